@@ -1,34 +1,153 @@
-use bevy::{ecs::system::SystemParam, platform::collections::HashMap, prelude::*};
+use std::{
+    borrow::Cow,
+    sync::{Arc, RwLock},
+};
 
-use crate::game_logic::ability::{Ability, AbilityId};
+use bevy::{
+    ecs::system::SystemParam, platform::collections::HashMap, prelude::*, utils::prelude::ShortName,
+};
+use derive_more::{Display, Error};
 
+use crate::{
+    abilities::definitions::{AbilityDefinition, AbilityTuning, EffectTarget},
+    game_logic::{
+        ability::{Ability, AbilityId, CooldownGroup, PerformAbility},
+        ability_stats::AbilityStatsInterface,
+        effects::{EffectFromStats, EffectInterface, EffectSource},
+    },
+};
+
+pub mod barrier;
 pub mod charged_strike;
+pub mod cleanse;
+pub mod definitions;
+pub mod haste;
+pub mod kick;
 pub mod needling_hex;
 pub mod prepared_block;
+pub mod rejuvenation;
+pub mod shield_bash;
+pub mod volatile_charge;
+pub mod weaken;
 pub mod weapon_attack;
+pub mod withering_aura;
+
+/// Shared by abilities that control the enemy, e.g., by interrupting or stunning them.
+pub const CONTROL_COOLDOWN_GROUP: CooldownGroup = CooldownGroup(Cow::Borrowed("control"));
 
 pub type AbilitySpawner = fn(&mut Commands) -> Entity;
 
-// AbilityCatalog maps AbilityId to a function that spawns the ability entity
+#[derive(Debug, Default)]
+struct AbilityCatalogEntries {
+    spawners: HashMap<AbilityId, AbilitySpawner>,
+    definitions: HashMap<AbilityId, Arc<AbilityDefinition>>,
+    tunings: HashMap<AbilityId, Arc<AbilityTuning>>,
+}
+
+// AbilityCatalog maps AbilityId to a function that spawns the ability entity, or to an
+// `AbilityDefinition` loaded from data. Data can only define `AbilityId::Custom` abilities, so it
+// can't replace built-in abilities, and lose the parts that only exist in code. Built-in abilities
+// can be tuned with an `AbilityTuning` instead, which is applied on top of any spawned ability.
 #[derive(Resource, Default, Clone)]
-pub struct AbilityCatalog(
-    pub std::sync::Arc<std::sync::RwLock<HashMap<AbilityId, AbilitySpawner>>>,
-);
+pub struct AbilityCatalog(Arc<RwLock<AbilityCatalogEntries>>);
 
 impl AbilityCatalog {
     pub fn register(&self, id: AbilityId, spawner: AbilitySpawner) {
-        self.0.write().unwrap().insert(id, spawner);
+        self.0.write().unwrap().spawners.insert(id, spawner);
     }
 
-    pub fn spawn(&self, id: AbilityId, commands: &mut Commands) -> Entity {
-        let spawner = *self
-            .0
-            .read()
+    pub fn register_definition(
+        &self,
+        definition: AbilityDefinition,
+    ) -> Result<(), BuiltinAbilityIdError> {
+        if !matches!(definition.id, AbilityId::Custom(_)) {
+            return Err(BuiltinAbilityIdError { id: definition.id });
+        }
+
+        self.0
+            .write()
+            .unwrap()
+            .definitions
+            .insert(definition.id.clone(), Arc::new(definition));
+        Ok(())
+    }
+
+    pub fn register_tuning(&self, tuning: AbilityTuning) {
+        self.0
+            .write()
             .unwrap()
-            .get(&id)
-            .unwrap_or_else(|| panic!("Ability not registered: {id:?}"));
-        spawner(commands)
+            .tunings
+            .insert(tuning.id.clone(), Arc::new(tuning));
     }
+
+    pub fn spawn(&self, id: AbilityId, commands: &mut Commands) -> Entity {
+        let entries = self.0.read().unwrap();
+
+        let ability_e = if let Some(spawner) = entries.spawners.get(&id) {
+            spawner(commands)
+        } else {
+            entries
+                .definitions
+                .get(&id)
+                .unwrap_or_else(|| panic!("Ability not registered: {id:?}"))
+                .spawn(commands)
+        };
+
+        if let Some(tuning) = entries.tunings.get(&id) {
+            tuning.apply(ability_e, commands);
+        }
+
+        ability_e
+    }
+}
+
+/// A definition used the ID of a built-in ability, instead of an [`AbilityId::Custom`] one.
+#[derive(Debug, Display, Error)]
+#[display("{id:?} is a built-in ability, definitions can only use Custom IDs")]
+pub struct BuiltinAbilityIdError {
+    pub id: AbilityId,
+}
+
+/// An ability that applies its [`EffectFromStats`] effect when it's performed. Its plugin adds
+/// [`apply_ability_effect`] as an observer.
+pub trait EffectApplyingAbility: Component {
+    type Effect: EffectFromStats;
+    /// Who receives the effect.
+    const TARGET: EffectTarget;
+}
+
+/// Applies the effect of `A`, created from its resolved stats, when `A` is performed.
+pub fn apply_ability_effect<A: EffectApplyingAbility>(
+    trigger: On<PerformAbility>,
+    mut effects_interface: EffectInterface<A::Effect>,
+    abilities: Query<(), With<A>>,
+    ability_stats: AbilityStatsInterface,
+) {
+    let event = trigger.event();
+
+    if !abilities.contains(event.ability_entity) {
+        return;
+    }
+
+    let Some(target_e) = A::TARGET.resolve(event) else {
+        error!(
+            "{} without {:?} - ignoring. Event: {event:?}",
+            ShortName::of::<A>(),
+            A::TARGET
+        );
+        return;
+    };
+
+    let stats = ability_stats.resolved_stats(event.ability_entity);
+    let Some((effect, timer)) = A::Effect::from_stats(&stats) else {
+        error!(
+            "{} with incomplete stats {stats:?} - ignoring. Event: {event:?}",
+            ShortName::of::<A>()
+        );
+        return;
+    };
+
+    effects_interface.apply_timed_effect(target_e, EffectSource::from(event), effect, timer);
 }
 
 #[derive(SystemParam)]
@@ -50,17 +169,29 @@ pub struct AbilitiesPlugin;
 impl Plugin for AbilitiesPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AbilityCatalog>().add_plugins((
+            definitions::AbilityDefinitionsPlugin,
             weapon_attack::WeaponAttackPlugin,
             needling_hex::NeedlingHexPlugin,
             charged_strike::ChargedStrikePlugin,
             prepared_block::PreparedBlockPlugin,
+            rejuvenation::RejuvenationPlugin,
+            shield_bash::ShieldBashPlugin,
+            kick::KickPlugin,
+            weaken::WeakenPlugin,
+            haste::HastePlugin,
+            cleanse::CleansePlugin,
+            barrier::BarrierPlugin,
+            withering_aura::WitheringAuraPlugin,
+            volatile_charge::VolatileChargePlugin,
         ));
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use bevy::prelude::*;
+    use std::time::Duration;
+
+    use bevy::{ecs::message::MessageCursor, prelude::*, time::TimeUpdateStrategy};
 
     use super::AbilityCatalog;
     use crate::{
@@ -69,12 +200,13 @@ mod tests {
             weapon_attack::WeaponAttackPlugin,
         },
         game_logic::{
-            ability::{Ability, AbilityId},
+            ability::{Ability, AbilityId, PerformAbility},
             ability_casting::AbilityCastingPlugin,
+            ability_stats::{AbilityStat, AbilityStats, StatValue},
             commands::CommandsPlugin,
-            damage_resolution::{DamageResolutionPlugin, DealDamage},
-            effects::HasEffects,
-            fight::FightPlugin,
+            damage_resolution::{DamageDealt, DamageResolutionPlugin, DealDamage},
+            effects::{EffectsPlugin, HasEffects},
+            fight::{FightPlugin, FightTime},
             ongoing_cast::{OngoingCastFinishedSuccessfully, OngoingCastPlugin},
         },
         test_utils::{TestFightEntities, spawn_test_fight},
@@ -134,6 +266,14 @@ mod tests {
                     description: "Needling Hex".into(),
                 },
                 NeedlingHexAbility,
+                AbilityStats::new([
+                    (AbilityStat::Damage, StatValue::Amount(5.0)),
+                    (
+                        AbilityStat::TickInterval,
+                        StatValue::Duration(Duration::from_millis(500)),
+                    ),
+                    (AbilityStat::NumTicks, StatValue::Count(5)),
+                ]),
             ))
             .id();
 
@@ -148,23 +288,91 @@ mod tests {
         app.update();
 
         // Verify NeedlingHexEffect
-        // NeedlingHexEffect is not on the target, but on a child of the holder.
+        // NeedlingHexEffect is not on the target, but on an effect entity related to it.
         // We check if HasEffects is present, and if we can find the effect.
 
         let has_effects = app.world().get::<HasEffects>(enemy_e);
         assert!(has_effects.is_some(), "Target should have HasEffects");
 
-        let holder_e = has_effects.unwrap().holder();
-        let children = app.world().get::<Children>(holder_e);
-        assert!(children.is_some(), "Holder should have children");
-
-        let found = children
+        let found = has_effects
             .unwrap()
+            .effects()
             .iter()
-            .any(|child| app.world().get::<NeedlingHexEffect>(child).is_some());
+            .any(|&effect_e| app.world().get::<NeedlingHexEffect>(effect_e).is_some());
         assert!(
             found,
-            "Should find NeedlingHexEffect on a child of the holder"
+            "Should find NeedlingHexEffect on an effect entity of the target"
         );
     }
+
+    #[test]
+    fn test_effect_damage_is_attributed_to_caster_and_ability() {
+        let mut app = App::new();
+        app.init_resource::<AbilityCatalog>()
+            .add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .add_plugins(DamageResolutionPlugin)
+            .add_plugins(NeedlingHexPlugin);
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            slot_e,
+            ability_e: _,
+            enemy_e,
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+
+        let hex_ability_e = app
+            .world_mut()
+            .spawn((
+                Ability {
+                    id: AbilityId::NeedlingHex,
+                    name: "Needling Hex".into(),
+                    description: "Needling Hex".into(),
+                },
+                NeedlingHexAbility,
+                AbilityStats::new([
+                    (AbilityStat::Damage, StatValue::Amount(5.0)),
+                    (
+                        AbilityStat::TickInterval,
+                        StatValue::Duration(Duration::from_millis(100)),
+                    ),
+                    (AbilityStat::NumTicks, StatValue::Count(1)),
+                ]),
+            ))
+            .id();
+
+        app.world_mut().trigger(PerformAbility {
+            ability_entity: hex_ability_e,
+            caster: Some(caster_e),
+            target: Some(enemy_e),
+            slot: slot_e,
+        });
+
+        // update once to initialize all systems etc., required when testing with manual time.
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            150,
+        )));
+        app.update();
+
+        let messages = app.world().resource::<Messages<DamageDealt>>();
+        let dealt = MessageCursor::default()
+            .read(messages)
+            .cloned()
+            .map(|dealt: DamageDealt| dealt.instance)
+            .collect::<Vec<_>>();
+
+        assert_eq!(dealt.len(), 1);
+        assert_eq!(dealt[0].target, enemy_e);
+        assert_eq!(dealt[0].source, Some(caster_e));
+        assert_eq!(dealt[0].source_ability, Some(hex_ability_e));
+    }
 }
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{
+    AbilityCatalog, EffectApplyingAbility, apply_ability_effect, definitions::EffectTarget,
+};
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
+        OnGlobalCooldown,
+    },
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStats, StatValue},
+    absorb_shield::AbsorbShieldEffect,
+    resource_pools::{AbilityCost, CostPayment, ResourceType},
+};
+
+// Marker component for barrier ability
+#[derive(Component, Debug, Reflect)]
+pub struct BarrierAbility;
+
+const THIS_ABILITY_ID: AbilityId = AbilityId::Barrier;
+const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(25);
+
+pub fn spawn_barrier(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Ability {
+                id: THIS_ABILITY_ID,
+                name: "Barrier".into(),
+                description: "Shield yourself with a magical barrier that absorbs up to {AbsorbAmount} damage within {EffectDuration}.".into(),
+            },
+            BarrierAbility,
+            AbilityStats::new([
+                (AbilityStat::AbsorbAmount, StatValue::Amount(30.0)),
+                (
+                    AbilityStat::EffectDuration,
+                    StatValue::Duration(Duration::from_secs(10)),
+                ),
+            ]),
+            AbilitySlotRequirement(AbilitySlotType::Magic),
+            AbilityCooldown {
+                duration: THIS_ABILITY_ABILITY_COOLDOWN,
+            },
+            OnGlobalCooldown,
+            AbilityCastTime(Duration::from_secs(1)),
+            AbilityCost {
+                resource: ResourceType::Mana,
+                amount: 30.0,
+                payment: CostPayment::OnCastFinish,
+            },
+        ))
+        .id()
+}
+
+fn register_ability(catalog: Res<AbilityCatalog>) {
+    catalog.register(THIS_ABILITY_ID, spawn_barrier);
+}
+
+impl EffectApplyingAbility for BarrierAbility {
+    type Effect = AbsorbShieldEffect;
+    const TARGET: EffectTarget = EffectTarget::Caster;
+}
+
+#[derive(Debug)]
+pub struct BarrierPlugin;
+
+impl Plugin for BarrierPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BarrierAbility>()
+            .add_systems(PreStartup, register_ability)
+            .add_observer(apply_ability_effect::<BarrierAbility>);
+    }
+}
@@ -7,10 +7,13 @@ use crate::{
     game_logic::{
         ability::{
             Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
-            PerformAbility,
+            OnGlobalCooldown, PerformAbility, ResetCooldownOnKill,
         },
         ability_slots::AbilitySlotType,
-        damage_resolution::{DamageInstance, DealDamage},
+        ability_stats::{AbilityStat, AbilityStats, AbilityStatsInterface, StatValue},
+        cooldown::{CooldownTiming, InterruptedCooldown},
+        damage_resolution::{DamageInstance, DamageType, DealDamage},
+        resource_pools::{AbilityCost, CostPayment, ResourceType},
     },
     utils::holds_held::Held,
 };
@@ -21,20 +24,32 @@ pub struct ChargedStrikeAbility;
 
 const THIS_ABILITY_ID: AbilityId = AbilityId::ChargedStrike;
 
-fn spawn_charged_strike(commands: &mut Commands) -> Entity {
+pub fn spawn_charged_strike(commands: &mut Commands) -> Entity {
     commands
         .spawn((
             Ability {
                 id: THIS_ABILITY_ID,
                 name: "Charged Strike".into(),
-                description: "Charge an extra strong strike, dealing 25 damage!".into(),
+                description: "Charge an extra strong strike, dealing {Damage} damage! Killing the target resets the cooldown.".into(),
             },
             ChargedStrikeAbility,
+            ResetCooldownOnKill,
+            AbilityStats::new([(AbilityStat::Damage, StatValue::Amount(25.0))]),
             AbilitySlotRequirement(AbilitySlotType::WeaponAttack),
             AbilityCooldown {
                 duration: Duration::from_secs(20),
             },
+            OnGlobalCooldown,
+            CooldownTiming {
+                on_interrupt: InterruptedCooldown::Partial(0.5),
+                ..default()
+            },
             AbilityCastTime(Duration::from_secs(2)),
+            AbilityCost {
+                resource: ResourceType::Stamina,
+                amount: 30.0,
+                payment: CostPayment::OnCastStart,
+            },
         ))
         .id()
 }
@@ -47,6 +62,7 @@ fn on_charged_strike(
     trigger: On<PerformAbility>,
     mut deal_damage_events: MessageWriter<DealDamage>,
     abilities: Query<&Held<Ability>, With<ChargedStrikeAbility>>,
+    ability_stats: AbilityStatsInterface,
 ) {
     let event = trigger.event();
 
@@ -64,10 +80,18 @@ fn on_charged_strike(
         return;
     };
 
+    let stats = ability_stats.resolved_stats(event.ability_entity);
+    let Some(damage) = stats.amount(AbilityStat::Damage) else {
+        error!("Charged Strike without Damage stat. Event: {event:?}");
+        return;
+    };
+
     deal_damage_events.write(DealDamage(DamageInstance {
         source: Some(caster_e),
+        source_ability: Some(event.ability_entity),
         target: target_e,
-        amount: 25.0,
+        amount: damage,
+        damage_type: DamageType::Physical,
     }));
 }
 
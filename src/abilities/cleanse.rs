@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::AbilityCatalog;
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
+        OnGlobalCooldown, PerformAbility,
+    },
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStats, AbilityStatsInterface, StatValue},
+    effects::{AnyEffectInterface, EffectCategory, EffectDisposition},
+    resource_pools::{AbilityCost, CostPayment, ResourceType},
+};
+
+// Marker component for cleanse ability
+#[derive(Component, Debug, Reflect)]
+pub struct CleanseAbility;
+
+const THIS_ABILITY_ID: AbilityId = AbilityId::Cleanse;
+const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(12);
+const DISPELLED_CATEGORIES: [EffectCategory; 2] = [EffectCategory::Magic, EffectCategory::Curse];
+
+pub fn spawn_cleanse(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Ability {
+                id: THIS_ABILITY_ID,
+                name: "Cleanse".into(),
+                description:
+                    "Cleanse yourself, removing up to {DispelCount} Magic or Curse debuffs.".into(),
+            },
+            CleanseAbility,
+            AbilityStats::new([(AbilityStat::DispelCount, StatValue::Count(2))]),
+            AbilitySlotRequirement(AbilitySlotType::Magic),
+            AbilityCooldown {
+                duration: THIS_ABILITY_ABILITY_COOLDOWN,
+            },
+            OnGlobalCooldown,
+            AbilityCastTime(Duration::ZERO),
+            AbilityCost {
+                resource: ResourceType::Mana,
+                amount: 20.0,
+                payment: CostPayment::OnCastFinish,
+            },
+        ))
+        .id()
+}
+
+fn register_ability(catalog: Res<AbilityCatalog>) {
+    catalog.register(THIS_ABILITY_ID, spawn_cleanse);
+}
+
+fn on_cleanse(
+    trigger: On<PerformAbility>,
+    mut effects_interface: AnyEffectInterface,
+    abilities: Query<(), With<CleanseAbility>>,
+    ability_stats: AbilityStatsInterface,
+) {
+    let event = trigger.event();
+
+    let Ok(_ability_e) = abilities.get(event.ability_entity) else {
+        return;
+    };
+
+    // Cleanse dispels the caster.
+    let Some(caster_e) = event.caster else {
+        error!("Cleanse without caster - ignoring. Event: {event:?}");
+        return;
+    };
+
+    let stats = ability_stats.resolved_stats(event.ability_entity);
+    let Some(dispel_count) = stats.count(AbilityStat::DispelCount) else {
+        error!("Cleanse with incomplete stats {stats:?} - ignoring. Event: {event:?}");
+        return;
+    };
+
+    effects_interface.dispel(
+        caster_e,
+        EffectDisposition::Debuff,
+        &DISPELLED_CATEGORIES,
+        dispel_count,
+    );
+}
+
+#[derive(Debug)]
+pub struct CleansePlugin;
+
+impl Plugin for CleansePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CleanseAbility>()
+            .add_systems(PreStartup, register_ability)
+            .add_observer(on_cleanse);
+    }
+}
@@ -0,0 +1,1005 @@
+//! Data-driven ability definitions, loaded from RON files (see [`ABILITY_DEFINITIONS_DIR`]) into
+//! the [`AbilityCatalog`].
+//!
+//! A definition describes the static parts of an ability (name, description, slot requirement,
+//! cooldown, cast time, cost, stats) and a list of [`AbilityEffectDefinition`]s that are executed
+//! when the ability is performed. The effects read their values from the ability's stats.
+//! Definitions can only use [`AbilityId::Custom`] IDs, the built-in abilities are defined in code
+//! and can only be tuned through an [`AbilityTuning`]. Example (see
+//! `assets/abilities/heavy_swing.ron`):
+//!
+//! ```ron
+//! (
+//!     id: Custom("heavy_swing"),
+//!     name: "Heavy Swing",
+//!     description: "A slow but powerful swing, dealing {Damage} damage.",
+//!     slot_requirement: Some((WeaponAttack)),
+//!     cooldown: Some((duration: (secs: 8, nanos: 0))),
+//!     on_global_cooldown: true,
+//!     cast_time: Some(((secs: 1, nanos: 0))),
+//!     cost: Some((resource: Stamina, amount: 20.0, payment: OnCastStart)),
+//!     stats: {Damage: Amount(15.0)},
+//!     effects: [DealDamage(damage_type: Physical)],
+//! )
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use derive_more::{Display, Error};
+use serde::Deserialize;
+
+use super::{
+    AbilityCatalog, needling_hex::NeedlingHexEffect, prepared_block::PreparedBlockEffect,
+    rejuvenation::RejuvenationEffect,
+};
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityCooldownGroup, AbilityId,
+        AbilitySlotRequirement, OnGlobalCooldown, PerformAbility, ResetCooldownOnKill,
+    },
+    ability_casting::AbilityCastingInterface,
+    ability_stats::{AbilityStat, AbilityStats, AbilityStatsInterface},
+    cooldown::{CooldownStart, CooldownTiming},
+    crowd_control::{DisarmEffect, SilenceEffect, StunEffect},
+    damage_resolution::{DamageInstance, DamageType, DealDamage},
+    effects::{
+        AnyEffectInterface, EffectCategory, EffectDisposition, EffectFromStats, EffectInterface,
+        EffectSource, EffectTimer,
+    },
+    healing::{Heal, HealInstance},
+    resource_pools::AbilityCost,
+};
+
+/// Directory (relative to the working directory) from which ability definitions are loaded.
+pub const ABILITY_DEFINITIONS_DIR: &str = "assets/abilities";
+
+/// Directory (relative to the working directory) from which [`AbilityTuning`]s are loaded.
+pub const ABILITY_TUNINGS_DIR: &str = "assets/abilities/tuning";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AbilityDefinition {
+    pub id: AbilityId,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub slot_requirement: Option<AbilitySlotRequirement>,
+    #[serde(default)]
+    pub cooldown: Option<AbilityCooldown>,
+    #[serde(default)]
+    pub cooldown_group: Option<AbilityCooldownGroup>,
+    #[serde(default)]
+    pub cooldown_timing: Option<CooldownTiming>,
+    /// Whether the ability is on the global cooldown, see [`OnGlobalCooldown`].
+    #[serde(default)]
+    pub on_global_cooldown: bool,
+    /// Whether killing the target resets the cooldown, see [`ResetCooldownOnKill`].
+    #[serde(default)]
+    pub reset_cooldown_on_kill: bool,
+    #[serde(default)]
+    pub cast_time: Option<AbilityCastTime>,
+    #[serde(default)]
+    pub cost: Option<AbilityCost>,
+    #[serde(default)]
+    pub stats: AbilityStats,
+    #[serde(default)]
+    pub effects: Vec<AbilityEffectDefinition>,
+}
+
+/// Effect primitives that a data-driven ability executes on [`PerformAbility`].
+#[derive(Debug, Clone, Reflect, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum AbilityEffectDefinition {
+    /// Deals the ability's [`AbilityStat::Damage`].
+    DealDamage {
+        #[serde(default)]
+        target: EffectTarget,
+        #[serde(default)]
+        damage_type: DamageType,
+    },
+    /// Heals for the ability's [`AbilityStat::Healing`].
+    Heal {
+        #[serde(default)]
+        target: EffectTarget,
+        /// Whether the heal resurrects dead targets.
+        #[serde(default)]
+        resurrect: bool,
+    },
+    /// Interrupts all ongoing casts, locking out the interrupted slots for the ability's
+    /// [`AbilityStat::EffectDuration`] (if any).
+    Interrupt {
+        #[serde(default)]
+        target: EffectTarget,
+    },
+    /// Dispels up to the ability's [`AbilityStat::DispelCount`] effects of `disposition` and one
+    /// of `categories`.
+    Dispel {
+        #[serde(default)]
+        target: EffectTarget,
+        disposition: EffectDisposition,
+        categories: Vec<EffectCategory>,
+    },
+    /// Applies the effect, which is created from the ability's stats.
+    ApplyEffect {
+        effect: EffectKind,
+        #[serde(default)]
+        target: EffectTarget,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Deserialize)]
+pub enum EffectTarget {
+    Caster,
+    #[default]
+    Target,
+}
+
+impl EffectTarget {
+    pub fn resolve(&self, event: &PerformAbility) -> Option<Entity> {
+        match self {
+            EffectTarget::Caster => event.caster,
+            EffectTarget::Target => event.target,
+        }
+    }
+}
+
+/// The effects that can be applied by data-driven abilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Deserialize)]
+pub enum EffectKind {
+    NeedlingHex,
+    PreparedBlock,
+    Rejuvenation,
+    /// Uses the ability's [`AbilityStat::EffectDuration`].
+    Stun,
+    /// Uses the ability's [`AbilityStat::EffectDuration`].
+    Silence,
+    /// Uses the ability's [`AbilityStat::EffectDuration`].
+    Disarm,
+}
+
+/// Overrides the numbers of an ability, including built-in ones, e.g., to balance them without
+/// recompiling. Example (see `assets/abilities/tuning/weapon_attack.ron`):
+///
+/// ```ron
+/// (
+///     id: WeaponAttack,
+///     cooldown: Some((duration: (secs: 5, nanos: 0))),
+///     stats: {Damage: Amount(10.0)},
+/// )
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AbilityTuning {
+    pub id: AbilityId,
+    /// Replaces the given stats, the ability keeps its other stats.
+    #[serde(default)]
+    pub stats: AbilityStats,
+    #[serde(default)]
+    pub cooldown: Option<AbilityCooldown>,
+    #[serde(default)]
+    pub cast_time: Option<AbilityCastTime>,
+}
+
+impl AbilityTuning {
+    pub fn apply(&self, ability_e: Entity, commands: &mut Commands) {
+        let mut ability = commands.entity(ability_e);
+
+        if !self.stats.is_empty() {
+            let stats = self.stats.clone();
+            ability
+                .entry::<AbilityStats>()
+                .or_default()
+                .and_modify(move |mut base_stats| {
+                    for (stat, value) in stats.iter() {
+                        base_stats.set(stat, value);
+                    }
+                });
+        }
+
+        if let Some(cooldown) = &self.cooldown {
+            ability.insert(cooldown.clone());
+        }
+
+        if let Some(cast_time) = &self.cast_time {
+            ability.insert(cast_time.clone());
+        }
+    }
+}
+
+/// Holds the effect primitives of an ability that was spawned from an [`AbilityDefinition`].
+#[derive(Debug, Clone, Component, Reflect)]
+pub struct AbilityEffects(pub Vec<AbilityEffectDefinition>);
+
+impl AbilityDefinition {
+    pub fn spawn(&self, commands: &mut Commands) -> Entity {
+        let mut ability = commands.spawn((
+            Ability {
+                id: self.id.clone(),
+                name: self.name.clone().into(),
+                description: self.description.clone().into(),
+            },
+            self.stats.clone(),
+            AbilityEffects(self.effects.clone()),
+            self.cast_time
+                .clone()
+                .unwrap_or(AbilityCastTime(std::time::Duration::ZERO)),
+        ));
+
+        if let Some(slot_requirement) = &self.slot_requirement {
+            ability.insert(slot_requirement.clone());
+        }
+
+        if let Some(cooldown) = &self.cooldown {
+            ability.insert(cooldown.clone());
+        }
+
+        if let Some(cooldown_group) = &self.cooldown_group {
+            ability.insert(cooldown_group.clone());
+        }
+
+        if let Some(cooldown_timing) = self.cooldown_timing {
+            ability.insert(cooldown_timing);
+        }
+
+        if self.on_global_cooldown {
+            ability.insert(OnGlobalCooldown);
+        }
+
+        if self.reset_cooldown_on_kill {
+            ability.insert(ResetCooldownOnKill);
+        }
+
+        if let Some(cost) = &self.cost {
+            ability.insert(cost.clone());
+        }
+
+        ability.id()
+    }
+}
+
+#[derive(Debug, Display, Error)]
+#[display("{}: {source}", path.display())]
+pub struct AbilityDefinitionError {
+    pub path: PathBuf,
+    pub source: AbilityDefinitionErrorKind,
+}
+
+#[derive(Debug, Display, Error, derive_more::From)]
+pub enum AbilityDefinitionErrorKind {
+    Io(std::io::Error),
+    // `ron`'s error contains the line/column, as well as the offending field.
+    Parse(ron::error::SpannedError),
+    Invalid(InvalidAbilityDefinition),
+}
+
+/// Definitions that parse, but can't work as intended.
+#[derive(Debug, Display, Error)]
+pub enum InvalidAbilityDefinition {
+    /// The cooldown would start once an effect of the ability ended, but it applies none.
+    #[display("cooldown_timing starts on EffectExpiry, but the ability has no ApplyEffect")]
+    EffectExpiryWithoutEffect,
+}
+
+impl AbilityDefinition {
+    fn validate(&self) -> Result<(), InvalidAbilityDefinition> {
+        let starts_on_effect_expiry = self
+            .cooldown_timing
+            .is_some_and(|timing| timing.start == CooldownStart::EffectExpiry);
+        let applies_effect = self
+            .effects
+            .iter()
+            .any(|effect| matches!(effect, AbilityEffectDefinition::ApplyEffect { .. }));
+
+        if starts_on_effect_expiry && !applies_effect {
+            return Err(InvalidAbilityDefinition::EffectExpiryWithoutEffect);
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse_ability_definition(
+    path: &Path,
+    contents: &str,
+) -> Result<AbilityDefinition, AbilityDefinitionError> {
+    let definition: AbilityDefinition =
+        ron::from_str(contents).map_err(|e| AbilityDefinitionError {
+            path: path.to_path_buf(),
+            source: e.into(),
+        })?;
+
+    definition.validate().map_err(|e| AbilityDefinitionError {
+        path: path.to_path_buf(),
+        source: e.into(),
+    })?;
+
+    Ok(definition)
+}
+
+pub fn parse_ability_tuning(
+    path: &Path,
+    contents: &str,
+) -> Result<AbilityTuning, AbilityDefinitionError> {
+    ron::from_str(contents).map_err(|e| AbilityDefinitionError {
+        path: path.to_path_buf(),
+        source: e.into(),
+    })
+}
+
+fn read_file(path: &Path) -> Result<String, AbilityDefinitionError> {
+    fs::read_to_string(path).map_err(|e| AbilityDefinitionError {
+        path: path.to_path_buf(),
+        source: e.into(),
+    })
+}
+
+pub fn load_ability_definition(path: &Path) -> Result<AbilityDefinition, AbilityDefinitionError> {
+    parse_ability_definition(path, &read_file(path)?)
+}
+
+pub fn load_ability_tuning(path: &Path) -> Result<AbilityTuning, AbilityDefinitionError> {
+    parse_ability_tuning(path, &read_file(path)?)
+}
+
+/// Returns all `*.ron` files in `dir`, ordered by their path, so loading them is deterministic.
+fn ron_files_in_dir(dir: &Path) -> Result<Vec<PathBuf>, AbilityDefinitionError> {
+    let entries = fs::read_dir(dir).map_err(|e| AbilityDefinitionError {
+        path: dir.to_path_buf(),
+        source: e.into(),
+    })?;
+
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Loads all `*.ron` files in `dir`, see [`ron_files_in_dir`].
+pub fn load_ability_definitions_from_dir(
+    dir: &Path,
+) -> Result<Vec<Result<AbilityDefinition, AbilityDefinitionError>>, AbilityDefinitionError> {
+    Ok(ron_files_in_dir(dir)?
+        .iter()
+        .map(|path| load_ability_definition(path))
+        .collect())
+}
+
+/// Loads all `*.ron` files in `dir`, see [`ron_files_in_dir`].
+pub fn load_ability_tunings_from_dir(
+    dir: &Path,
+) -> Result<Vec<Result<AbilityTuning, AbilityDefinitionError>>, AbilityDefinitionError> {
+    Ok(ron_files_in_dir(dir)?
+        .iter()
+        .map(|path| load_ability_tuning(path))
+        .collect())
+}
+
+fn load_ability_definitions(catalog: Res<AbilityCatalog>) {
+    let dir = Path::new(ABILITY_DEFINITIONS_DIR);
+
+    if !dir.is_dir() {
+        info!("No ability definitions directory at '{}'", dir.display());
+        return;
+    }
+
+    let definitions = match load_ability_definitions_from_dir(dir) {
+        Ok(definitions) => definitions,
+        Err(e) => {
+            error!("Could not load ability definitions: {e}");
+            return;
+        }
+    };
+
+    for definition in definitions {
+        match definition {
+            Ok(definition) => {
+                let id = definition.id.clone();
+                match catalog.register_definition(definition) {
+                    Ok(()) => info!("Loaded ability definition for {id:?}"),
+                    Err(e) => error!("Invalid ability definition: {e}"),
+                }
+            }
+            Err(e) => error!("Invalid ability definition: {e}"),
+        }
+    }
+}
+
+fn load_ability_tunings(catalog: Res<AbilityCatalog>) {
+    let dir = Path::new(ABILITY_TUNINGS_DIR);
+
+    if !dir.is_dir() {
+        info!("No ability tunings directory at '{}'", dir.display());
+        return;
+    }
+
+    let tunings = match load_ability_tunings_from_dir(dir) {
+        Ok(tunings) => tunings,
+        Err(e) => {
+            error!("Could not load ability tunings: {e}");
+            return;
+        }
+    };
+
+    for tuning in tunings {
+        match tuning {
+            Ok(tuning) => {
+                info!("Loaded ability tuning for {:?}", tuning.id);
+                catalog.register_tuning(tuning);
+            }
+            Err(e) => error!("Invalid ability tuning: {e}"),
+        }
+    }
+}
+
+/// The interfaces for applying each [`EffectKind`], and for dispelling effects of any kind.
+#[derive(SystemParam)]
+struct EffectKindInterfaces<'w, 's> {
+    any: AnyEffectInterface<'w, 's>,
+    needling_hex: EffectInterface<'w, 's, NeedlingHexEffect>,
+    prepared_block: EffectInterface<'w, 's, PreparedBlockEffect>,
+    rejuvenation: EffectInterface<'w, 's, RejuvenationEffect>,
+    stun: EffectInterface<'w, 's, StunEffect>,
+    silence: EffectInterface<'w, 's, SilenceEffect>,
+    disarm: EffectInterface<'w, 's, DisarmEffect>,
+}
+
+fn on_data_driven_ability(
+    trigger: On<PerformAbility>,
+    abilities: Query<&AbilityEffects>,
+    ability_stats: AbilityStatsInterface,
+    mut deal_damage_events: MessageWriter<DealDamage>,
+    mut heal_events: MessageWriter<Heal>,
+    mut effect_interfaces: EffectKindInterfaces,
+    mut ability_casting_interface: AbilityCastingInterface,
+) {
+    let event = trigger.event();
+
+    let Ok(AbilityEffects(effects)) = abilities.get(event.ability_entity) else {
+        return;
+    };
+
+    let stats = ability_stats.resolved_stats(event.ability_entity);
+
+    for effect in effects {
+        match effect {
+            AbilityEffectDefinition::DealDamage {
+                target,
+                damage_type,
+            } => {
+                let Some(target_e) = target.resolve(event) else {
+                    error!("DealDamage without {target:?} - ignoring. Event: {event:?}");
+                    continue;
+                };
+
+                let Some(amount) = stats.amount(AbilityStat::Damage) else {
+                    error!("DealDamage without Damage stat - ignoring. Event: {event:?}");
+                    continue;
+                };
+
+                deal_damage_events.write(DealDamage(DamageInstance {
+                    source: event.caster,
+                    source_ability: Some(event.ability_entity),
+                    target: target_e,
+                    amount,
+                    damage_type: *damage_type,
+                }));
+            }
+            AbilityEffectDefinition::Heal { target, resurrect } => {
+                let Some(target_e) = target.resolve(event) else {
+                    error!("Heal without {target:?} - ignoring. Event: {event:?}");
+                    continue;
+                };
+
+                let Some(amount) = stats.amount(AbilityStat::Healing) else {
+                    error!("Heal without Healing stat - ignoring. Event: {event:?}");
+                    continue;
+                };
+
+                heal_events.write(Heal(HealInstance {
+                    source: event.caster,
+                    source_ability: Some(event.ability_entity),
+                    target: target_e,
+                    amount,
+                    resurrect: *resurrect,
+                }));
+            }
+            AbilityEffectDefinition::Interrupt { target } => {
+                let Some(target_e) = target.resolve(event) else {
+                    error!("Interrupt without {target:?} - ignoring. Event: {event:?}");
+                    continue;
+                };
+
+                ability_casting_interface.interrupt_casts_of(
+                    target_e,
+                    event.caster,
+                    stats.duration(AbilityStat::EffectDuration),
+                );
+            }
+            AbilityEffectDefinition::Dispel {
+                target,
+                disposition,
+                categories,
+            } => {
+                let Some(target_e) = target.resolve(event) else {
+                    error!("Dispel without {target:?} - ignoring. Event: {event:?}");
+                    continue;
+                };
+
+                let Some(dispel_count) = stats.count(AbilityStat::DispelCount) else {
+                    error!("Dispel without DispelCount stat - ignoring. Event: {event:?}");
+                    continue;
+                };
+
+                effect_interfaces
+                    .any
+                    .dispel(target_e, *disposition, categories, dispel_count);
+            }
+            AbilityEffectDefinition::ApplyEffect { effect, target } => {
+                let Some(target_e) = target.resolve(event) else {
+                    error!("ApplyEffect without {target:?} - ignoring. Event: {event:?}");
+                    continue;
+                };
+
+                let cc_timer = || {
+                    stats
+                        .duration(AbilityStat::EffectDuration)
+                        .map(EffectTimer::duration)
+                };
+
+                let applied = match effect {
+                    EffectKind::NeedlingHex => {
+                        NeedlingHexEffect::from_stats(&stats).map(|(e, timer)| {
+                            effect_interfaces.needling_hex.apply_timed_effect(
+                                target_e,
+                                EffectSource::from(event),
+                                e,
+                                timer,
+                            )
+                        })
+                    }
+                    EffectKind::PreparedBlock => {
+                        PreparedBlockEffect::from_stats(&stats).map(|(e, timer)| {
+                            effect_interfaces.prepared_block.apply_timed_effect(
+                                target_e,
+                                EffectSource::from(event),
+                                e,
+                                timer,
+                            )
+                        })
+                    }
+                    EffectKind::Rejuvenation => {
+                        RejuvenationEffect::from_stats(&stats).map(|(e, timer)| {
+                            effect_interfaces.rejuvenation.apply_timed_effect(
+                                target_e,
+                                EffectSource::from(event),
+                                e,
+                                timer,
+                            )
+                        })
+                    }
+                    EffectKind::Stun => cc_timer().map(|timer| {
+                        effect_interfaces.stun.apply_timed_effect(
+                            target_e,
+                            EffectSource::from(event),
+                            StunEffect,
+                            timer,
+                        )
+                    }),
+                    EffectKind::Silence => cc_timer().map(|timer| {
+                        effect_interfaces.silence.apply_timed_effect(
+                            target_e,
+                            EffectSource::from(event),
+                            SilenceEffect,
+                            timer,
+                        )
+                    }),
+                    EffectKind::Disarm => cc_timer().map(|timer| {
+                        effect_interfaces.disarm.apply_timed_effect(
+                            target_e,
+                            EffectSource::from(event),
+                            DisarmEffect,
+                            timer,
+                        )
+                    }),
+                };
+
+                if applied.is_none() {
+                    error!(
+                        "{effect:?} with incomplete stats {stats:?} - ignoring. Event: {event:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AbilityDefinitionsPlugin;
+
+impl Plugin for AbilityDefinitionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AbilityEffects>()
+            .add_systems(PreStartup, (load_ability_definitions, load_ability_tunings))
+            .add_observer(on_data_driven_ability);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, time::Duration};
+
+    use bevy::prelude::*;
+
+    use super::{
+        ABILITY_DEFINITIONS_DIR, ABILITY_TUNINGS_DIR, AbilityDefinitionErrorKind,
+        AbilityEffectDefinition, EffectKind, EffectTarget, InvalidAbilityDefinition,
+        load_ability_definitions_from_dir, load_ability_tunings_from_dir, parse_ability_definition,
+        parse_ability_tuning,
+    };
+    use crate::{
+        abilities::{
+            AbilityCatalog, AbilitySpawner, charged_strike, cleanse, kick,
+            needling_hex::{self, NeedlingHexAbility},
+            prepared_block, rejuvenation, shield_bash, weapon_attack,
+        },
+        game_logic::{
+            ability::{
+                Ability, AbilityCastTime, AbilityCooldown, AbilityCooldownGroup, AbilityId,
+                AbilitySlotRequirement, CooldownGroup, OnGlobalCooldown, ResetCooldownOnKill,
+            },
+            ability_slots::AbilitySlotType,
+            ability_stats::{AbilityStat, AbilityStats, StatValue},
+            cooldown::CooldownTiming,
+            resource_pools::AbilityCost,
+        },
+    };
+
+    const WEAPON_ATTACK: &str = r#"(
+        id: WeaponAttack,
+        name: "Attack",
+        description: "Strike with your weapon, dealing {Damage} damage.",
+        slot_requirement: Some((WeaponAttack)),
+        cooldown: Some((duration: (secs: 5, nanos: 0))),
+        cast_time: Some(((secs: 0, nanos: 0))),
+        stats: {Damage: Amount(10.0)},
+        effects: [DealDamage(damage_type: Physical)],
+    )"#;
+
+    const NEEDLING_HEX: &str = r#"(
+        id: NeedlingHex,
+        name: "Needling Hex",
+        description: "Hex your enemy with repeated damage over time.",
+        slot_requirement: Some((Magic)),
+        cooldown: Some((duration: (secs: 30, nanos: 0))),
+        on_global_cooldown: true,
+        cast_time: Some(((secs: 0, nanos: 0))),
+        cost: Some((resource: Mana, amount: 40.0)),
+        stats: {
+            Damage: Amount(5.0),
+            TickInterval: Duration((secs: 0, nanos: 500000000)),
+            NumTicks: Count(5),
+        },
+        effects: [ApplyEffect(effect: NeedlingHex)],
+    )"#;
+
+    const CHARGED_STRIKE: &str = r#"(
+        id: ChargedStrike,
+        name: "Charged Strike",
+        description: "Charge an extra strong strike, dealing {Damage} damage! Killing the target resets the cooldown.",
+        slot_requirement: Some((WeaponAttack)),
+        cooldown: Some((duration: (secs: 20, nanos: 0))),
+        cooldown_timing: Some((on_interrupt: Partial(0.5))),
+        on_global_cooldown: true,
+        reset_cooldown_on_kill: true,
+        cast_time: Some(((secs: 2, nanos: 0))),
+        cost: Some((resource: Stamina, amount: 30.0, payment: OnCastStart)),
+        stats: {Damage: Amount(25.0)},
+        effects: [DealDamage(damage_type: Physical)],
+    )"#;
+
+    const PREPARED_BLOCK: &str = r#"(
+        id: PreparedBlock,
+        name: "Prepared Block",
+        description: "Prepare to block the next hit you would take within {EffectDuration}, absorbing up to {AbsorbAmount} damage.",
+        slot_requirement: Some((ShieldDefend)),
+        cooldown: Some((duration: (secs: 30, nanos: 0))),
+        cooldown_timing: Some((start: EffectExpiry)),
+        cast_time: Some(((secs: 1, nanos: 0))),
+        stats: {
+            AbsorbAmount: Amount(20.0),
+            EffectDuration: Duration((secs: 4, nanos: 0)),
+        },
+        effects: [ApplyEffect(effect: PreparedBlock, target: Caster)],
+    )"#;
+
+    const REJUVENATION: &str = r#"(
+        id: Rejuvenation,
+        name: "Rejuvenation",
+        description: "Heal yourself for {Healing} health every {TickInterval}.",
+        slot_requirement: Some((Magic)),
+        cooldown: Some((duration: (secs: 15, nanos: 0))),
+        on_global_cooldown: true,
+        cast_time: Some(((secs: 1, nanos: 0))),
+        cost: Some((resource: Mana, amount: 25.0)),
+        stats: {
+            Healing: Amount(4.0),
+            TickInterval: Duration((secs: 1, nanos: 0)),
+            NumTicks: Count(5),
+        },
+        effects: [ApplyEffect(effect: Rejuvenation, target: Caster)],
+    )"#;
+
+    const SHIELD_BASH: &str = r#"(
+        id: ShieldBash,
+        name: "Shield Bash",
+        description: "Bash your enemy with your shield, stunning them for {EffectDuration}.",
+        slot_requirement: Some((ShieldDefend)),
+        cooldown: Some((duration: (secs: 20, nanos: 0))),
+        cooldown_group: Some((group: "control", duration: (secs: 5, nanos: 0))),
+        on_global_cooldown: true,
+        cast_time: Some(((secs: 0, nanos: 0))),
+        cost: Some((resource: Stamina, amount: 20.0)),
+        stats: {EffectDuration: Duration((secs: 2, nanos: 0))},
+        effects: [ApplyEffect(effect: Stun)],
+    )"#;
+
+    const KICK: &str = r#"(
+        id: Kick,
+        name: "Kick",
+        description: "Kick your enemy, interrupting their casts and locking the interrupted slots for {EffectDuration}.",
+        slot_requirement: Some((WeaponAttack)),
+        cooldown: Some((duration: (secs: 15, nanos: 0))),
+        cooldown_group: Some((group: "control", duration: (secs: 5, nanos: 0))),
+        cast_time: Some(((secs: 0, nanos: 0))),
+        stats: {EffectDuration: Duration((secs: 3, nanos: 0))},
+        effects: [Interrupt()],
+    )"#;
+
+    const CLEANSE: &str = r#"(
+        id: Cleanse,
+        name: "Cleanse",
+        description: "Cleanse yourself, removing up to {DispelCount} Magic or Curse debuffs.",
+        slot_requirement: Some((Magic)),
+        cooldown: Some((duration: (secs: 12, nanos: 0))),
+        on_global_cooldown: true,
+        cast_time: Some(((secs: 0, nanos: 0))),
+        cost: Some((resource: Mana, amount: 20.0)),
+        stats: {DispelCount: Count(2)},
+        effects: [Dispel(target: Caster, disposition: Debuff, categories: [Magic, Curse])],
+    )"#;
+
+    type StaticAbilityParts = (
+        String,
+        String,
+        Option<AbilitySlotType>,
+        Option<Duration>,
+        Option<(CooldownGroup, Duration)>,
+        Option<CooldownTiming>,
+        bool,
+        bool,
+        Option<Duration>,
+        Option<AbilityCost>,
+        Vec<(AbilityStat, StatValue)>,
+    );
+
+    fn static_parts(world: &World, ability_e: Entity) -> StaticAbilityParts {
+        let ability = world.get::<Ability>(ability_e).unwrap();
+
+        (
+            ability.name.to_string(),
+            ability.description.to_string(),
+            world.get::<AbilitySlotRequirement>(ability_e).map(|r| r.0),
+            world.get::<AbilityCooldown>(ability_e).map(|c| c.duration),
+            world
+                .get::<AbilityCooldownGroup>(ability_e)
+                .map(|g| (g.group.clone(), g.duration)),
+            world.get::<CooldownTiming>(ability_e).copied(),
+            world.get::<OnGlobalCooldown>(ability_e).is_some(),
+            world.get::<ResetCooldownOnKill>(ability_e).is_some(),
+            world.get::<AbilityCastTime>(ability_e).map(|c| c.0),
+            world.get::<AbilityCost>(ability_e).cloned(),
+            world
+                .get::<AbilityStats>(ability_e)
+                .map(|s| s.iter().collect())
+                .unwrap_or_default(),
+        )
+    }
+
+    #[test]
+    fn test_builtin_abilities_are_expressible() {
+        let builtins: [(&str, AbilitySpawner); 8] = [
+            (WEAPON_ATTACK, weapon_attack::spawn_weapon_attack),
+            (NEEDLING_HEX, needling_hex::spawn_needling_hex),
+            (CHARGED_STRIKE, charged_strike::spawn_charged_strike),
+            (PREPARED_BLOCK, prepared_block::spawn_prepared_block),
+            (REJUVENATION, rejuvenation::spawn_rejuvenation),
+            (SHIELD_BASH, shield_bash::spawn_shield_bash),
+            (KICK, kick::spawn_kick),
+            (CLEANSE, cleanse::spawn_cleanse),
+        ];
+
+        for (ron_str, native_spawner) in builtins {
+            let definition = parse_ability_definition(Path::new("builtin.ron"), ron_str).unwrap();
+
+            let mut world = World::new();
+            let (native_e, data_e) = {
+                let mut commands = world.commands();
+                (
+                    native_spawner(&mut commands),
+                    definition.spawn(&mut commands),
+                )
+            };
+            world.flush();
+
+            assert_eq!(
+                world.get::<Ability>(native_e).unwrap().id,
+                world.get::<Ability>(data_e).unwrap().id
+            );
+            assert_eq!(static_parts(&world, native_e), static_parts(&world, data_e));
+        }
+    }
+
+    #[test]
+    fn test_definition_defaults() {
+        let definition = parse_ability_definition(
+            Path::new("hex.ron"),
+            r#"(
+                id: Custom("weak_hex"),
+                name: "Weak Hex",
+                description: "",
+                effects: [ApplyEffect(effect: NeedlingHex)],
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(definition.id, AbilityId::Custom("weak_hex".into()));
+        assert!(definition.slot_requirement.is_none());
+        assert!(definition.cooldown.is_none());
+        assert!(definition.cast_time.is_none());
+        assert!(matches!(
+            definition.effects.as_slice(),
+            [AbilityEffectDefinition::ApplyEffect {
+                effect: EffectKind::NeedlingHex,
+                target: EffectTarget::Target
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_errors_have_file_and_field_context() {
+        let err = parse_ability_definition(
+            Path::new("assets/abilities/broken.ron"),
+            r#"(
+                id: Custom("broken"),
+                name: "Broken",
+                description: "",
+                cooldown: Some((duraton: (secs: 1, nanos: 0))),
+            )"#,
+        )
+        .unwrap_err();
+
+        let msg = err.to_string();
+        assert!(msg.contains("assets/abilities/broken.ron"), "{msg}");
+        assert!(msg.contains("5:"), "should contain the line: {msg}");
+        assert!(msg.contains("duraton"), "should contain the field: {msg}");
+    }
+
+    #[test]
+    fn test_effect_expiry_cooldown_requires_an_applied_effect() {
+        let err = parse_ability_definition(
+            Path::new("assets/abilities/strike.ron"),
+            r#"(
+                id: Custom("strike"),
+                name: "Strike",
+                description: "",
+                cooldown: Some((duration: (secs: 5, nanos: 0))),
+                cooldown_timing: Some((start: EffectExpiry)),
+                stats: {Damage: Amount(10.0)},
+                effects: [DealDamage(damage_type: Physical)],
+            )"#,
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err.source,
+            AbilityDefinitionErrorKind::Invalid(
+                InvalidAbilityDefinition::EffectExpiryWithoutEffect
+            )
+        ));
+        assert!(err.to_string().contains("assets/abilities/strike.ron"));
+    }
+
+    #[test]
+    fn test_definitions_cant_replace_builtin_abilities() {
+        let catalog = AbilityCatalog::default();
+        catalog.register(AbilityId::WeaponAttack, weapon_attack::spawn_weapon_attack);
+
+        let err = catalog
+            .register_definition(
+                parse_ability_definition(
+                    Path::new("attack.ron"),
+                    &WEAPON_ATTACK.replace("\"Attack\"", "\"Tuned Attack\""),
+                )
+                .unwrap(),
+            )
+            .unwrap_err();
+        assert_eq!(err.id, AbilityId::WeaponAttack);
+
+        let mut world = World::new();
+        let ability_e = catalog.spawn(AbilityId::WeaponAttack, &mut world.commands());
+        world.flush();
+
+        assert_eq!(world.get::<Ability>(ability_e).unwrap().name, "Attack");
+    }
+
+    #[test]
+    fn test_tunings_override_numbers_of_builtin_abilities() {
+        let catalog = AbilityCatalog::default();
+        catalog.register(AbilityId::NeedlingHex, needling_hex::spawn_needling_hex);
+        catalog.register_tuning(
+            parse_ability_tuning(
+                Path::new("needling_hex.ron"),
+                r#"(
+                    id: NeedlingHex,
+                    cooldown: Some((duration: (secs: 20, nanos: 0))),
+                    cast_time: Some(((secs: 1, nanos: 0))),
+                    stats: {Damage: Amount(8.0)},
+                )"#,
+            )
+            .unwrap(),
+        );
+
+        let mut world = World::new();
+        let ability_e = catalog.spawn(AbilityId::NeedlingHex, &mut world.commands());
+        world.flush();
+
+        let stats = world.get::<AbilityStats>(ability_e).unwrap();
+        assert_eq!(stats.amount(AbilityStat::Damage), Some(8.0));
+        assert_eq!(stats.count(AbilityStat::NumTicks), Some(5));
+        assert_eq!(
+            world.get::<AbilityCooldown>(ability_e).unwrap().duration,
+            Duration::from_secs(20)
+        );
+        assert_eq!(
+            world.get::<AbilityCastTime>(ability_e).unwrap().0,
+            Duration::from_secs(1)
+        );
+        // everything else stays native
+        assert_eq!(
+            world.get::<Ability>(ability_e).unwrap().name,
+            "Needling Hex"
+        );
+        assert!(world.get::<NeedlingHexAbility>(ability_e).is_some());
+    }
+
+    #[test]
+    fn test_shipped_definitions_are_valid() {
+        let catalog = AbilityCatalog::default();
+        let definitions =
+            load_ability_definitions_from_dir(Path::new(ABILITY_DEFINITIONS_DIR)).unwrap();
+        assert!(!definitions.is_empty());
+
+        for definition in definitions {
+            let definition = definition.unwrap();
+            let id = definition.id.clone();
+            catalog.register_definition(definition).unwrap();
+
+            let mut world = World::new();
+            let ability_e = catalog.spawn(id.clone(), &mut world.commands());
+            world.flush();
+
+            assert_eq!(world.get::<Ability>(ability_e).unwrap().id, id);
+        }
+
+        let tunings = load_ability_tunings_from_dir(Path::new(ABILITY_TUNINGS_DIR)).unwrap();
+        assert!(!tunings.is_empty());
+        assert!(tunings.iter().all(Result::is_ok));
+    }
+}
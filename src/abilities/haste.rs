@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{
+    AbilityCatalog, EffectApplyingAbility, apply_ability_effect, definitions::EffectTarget,
+};
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
+        OnGlobalCooldown,
+    },
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStatModifier, AbilityStats, StatModifierKind, StatValue},
+    character_stats::{
+        CharacterStat, CharacterStatModifier, StatModifyingEffect, insert_stat_modifiers,
+    },
+    effects::{
+        EffectCategory, EffectClassification, EffectFromStats, EffectTimer, GameEffect,
+        ReflectGameEffect, StackableEffect, StackingPolicy,
+    },
+    resource_pools::{AbilityCost, CostPayment, ResourceType},
+};
+
+// Marker component for haste ability
+#[derive(Component, Debug, Reflect)]
+pub struct HasteAbility;
+
+const THIS_ABILITY_ID: AbilityId = AbilityId::Haste;
+const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(45);
+
+pub fn spawn_haste(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Ability {
+                id: THIS_ABILITY_ID,
+                name: "Haste".into(),
+                description: "Hasten yourself for {EffectDuration}, reducing your cast times and the tick intervals of your abilities to {CastTimeMultiplier}, and recovering your cooldowns at {CooldownRateMultiplier} speed.".into(),
+            },
+            HasteAbility,
+            AbilityStats::new([
+                (
+                    AbilityStat::EffectDuration,
+                    StatValue::Duration(Duration::from_secs(10)),
+                ),
+                (AbilityStat::CastTimeMultiplier, StatValue::Multiplier(0.7)),
+                (
+                    AbilityStat::CooldownRateMultiplier,
+                    StatValue::Multiplier(1.5),
+                ),
+            ]),
+            AbilitySlotRequirement(AbilitySlotType::Magic),
+            AbilityCooldown {
+                duration: THIS_ABILITY_ABILITY_COOLDOWN,
+            },
+            OnGlobalCooldown,
+            AbilityCastTime(Duration::ZERO),
+            AbilityCost {
+                resource: ResourceType::Mana,
+                amount: 30.0,
+                payment: CostPayment::OnCastFinish,
+            },
+        ))
+        .id()
+}
+
+fn register_ability(catalog: Res<AbilityCatalog>) {
+    catalog.register(THIS_ABILITY_ID, spawn_haste);
+}
+
+/// Reduces the cast times of its holder and the tick intervals of its abilities, and speeds up the
+/// recovery of its cooldowns.
+#[derive(Debug, Component, Reflect)]
+#[reflect(GameEffect)]
+pub struct HasteEffect {
+    pub cast_time_multiplier: f64,
+    pub cooldown_rate_multiplier: f64,
+}
+
+impl GameEffect for HasteEffect {}
+
+impl StackableEffect for HasteEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::RefreshDuration;
+    const CLASSIFICATION: EffectClassification = EffectClassification::buff(EffectCategory::Magic);
+}
+
+impl StatModifyingEffect for HasteEffect {
+    fn stat_modifiers(&self) -> Vec<CharacterStatModifier> {
+        vec![
+            CharacterStatModifier {
+                stat: CharacterStat::CastTime,
+                kind: StatModifierKind::Multiply(self.cast_time_multiplier),
+            },
+            CharacterStatModifier {
+                stat: CharacterStat::CooldownRate,
+                kind: StatModifierKind::Multiply(self.cooldown_rate_multiplier),
+            },
+        ]
+    }
+
+    fn ability_stat_modifiers(&self) -> Vec<AbilityStatModifier> {
+        vec![AbilityStatModifier {
+            ability: None,
+            stat: AbilityStat::TickInterval,
+            kind: StatModifierKind::Multiply(self.cast_time_multiplier),
+        }]
+    }
+}
+
+impl EffectFromStats for HasteEffect {
+    fn from_stats(stats: &AbilityStats) -> Option<(Self, EffectTimer)> {
+        Some((
+            HasteEffect {
+                cast_time_multiplier: stats.multiplier(AbilityStat::CastTimeMultiplier)?,
+                cooldown_rate_multiplier: stats.multiplier(AbilityStat::CooldownRateMultiplier)?,
+            },
+            EffectTimer::duration(stats.duration(AbilityStat::EffectDuration)?),
+        ))
+    }
+}
+
+impl EffectApplyingAbility for HasteAbility {
+    type Effect = HasteEffect;
+    const TARGET: EffectTarget = EffectTarget::Caster;
+}
+
+#[derive(Debug)]
+pub struct HastePlugin;
+
+impl Plugin for HastePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HasteEffect>()
+            .register_type::<HasteAbility>()
+            .add_systems(PreStartup, register_ability)
+            .add_observer(apply_ability_effect::<HasteAbility>)
+            .add_observer(insert_stat_modifiers::<HasteEffect>);
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{AbilityCatalog, CONTROL_COOLDOWN_GROUP};
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityCooldownGroup, AbilityId,
+        AbilitySlotRequirement, PerformAbility,
+    },
+    ability_casting::AbilityCastingInterface,
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStats, AbilityStatsInterface, StatValue},
+};
+
+// Marker component for kick ability
+#[derive(Component, Debug, Reflect)]
+pub struct KickAbility;
+
+const THIS_ABILITY_ID: AbilityId = AbilityId::Kick;
+const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(15);
+const THIS_ABILITY_GROUP_COOLDOWN: Duration = Duration::from_secs(5);
+
+pub fn spawn_kick(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Ability {
+                id: THIS_ABILITY_ID,
+                name: "Kick".into(),
+                description: "Kick your enemy, interrupting their casts and locking the interrupted slots for {EffectDuration}.".into(),
+            },
+            KickAbility,
+            AbilityStats::new([(
+                AbilityStat::EffectDuration,
+                StatValue::Duration(Duration::from_secs(3)),
+            )]),
+            AbilitySlotRequirement(AbilitySlotType::WeaponAttack),
+            AbilityCooldown {
+                duration: THIS_ABILITY_ABILITY_COOLDOWN,
+            },
+            AbilityCooldownGroup {
+                group: CONTROL_COOLDOWN_GROUP,
+                duration: THIS_ABILITY_GROUP_COOLDOWN,
+            },
+            AbilityCastTime(Duration::ZERO),
+        ))
+        .id()
+}
+
+fn register_ability(catalog: Res<AbilityCatalog>) {
+    catalog.register(THIS_ABILITY_ID, spawn_kick);
+}
+
+fn on_kick(
+    trigger: On<PerformAbility>,
+    mut ability_casting_interface: AbilityCastingInterface,
+    abilities: Query<(), With<KickAbility>>,
+    ability_stats: AbilityStatsInterface,
+) {
+    let event = trigger.event();
+
+    let Ok(_ability_e) = abilities.get(event.ability_entity) else {
+        return;
+    };
+
+    let Some(target_e) = event.target else {
+        error!("Kick without target - ignoring. Event: {event:?}");
+        return;
+    };
+
+    let stats = ability_stats.resolved_stats(event.ability_entity);
+    let Some(lockout) = stats.duration(AbilityStat::EffectDuration) else {
+        error!("Kick with incomplete stats {stats:?} - ignoring. Event: {event:?}");
+        return;
+    };
+
+    ability_casting_interface.interrupt_casts_of(target_e, event.caster, Some(lockout));
+}
+
+#[derive(Debug)]
+pub struct KickPlugin;
+
+impl Plugin for KickPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<KickAbility>()
+            .add_systems(PreStartup, register_ability)
+            .add_observer(on_kick);
+    }
+}
@@ -2,20 +2,22 @@ use std::time::Duration;
 
 use bevy::prelude::*;
 
-use super::AbilityCatalog;
-use crate::{
-    PerUpdateSet,
-    game_logic::{
-        ability::{
-            Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
-            PerformAbility,
-        },
-        ability_slots::AbilitySlotType,
-        damage_resolution::{DamageInstance, DealDamage},
-        effects::{GameEffect, ReflectGameEffect, UniqueEffectInterface},
-        fight::FightInterface,
+use super::{
+    AbilityCatalog, EffectApplyingAbility, apply_ability_effect, definitions::EffectTarget,
+};
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
+        OnGlobalCooldown,
+    },
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStats, StatValue},
+    damage_resolution::{DamageInstance, DamageType, DealDamage},
+    effects::{
+        EffectCategory, EffectClassification, EffectFromStats, EffectSource, EffectStacks,
+        EffectTicked, EffectTimer, GameEffect, ReflectGameEffect, StackableEffect, StackingPolicy,
     },
-    utils::FiniteRepeatingTimer,
+    resource_pools::{AbilityCost, CostPayment, ResourceType},
 };
 
 // Marker component for needling hex ability
@@ -25,7 +27,7 @@ pub struct NeedlingHexAbility;
 const THIS_ABILITY_ID: AbilityId = AbilityId::NeedlingHex;
 const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(30);
 
-fn spawn_needling_hex(commands: &mut Commands) -> Entity {
+pub fn spawn_needling_hex(commands: &mut Commands) -> Entity {
     commands
         .spawn((
             Ability {
@@ -34,11 +36,25 @@ fn spawn_needling_hex(commands: &mut Commands) -> Entity {
                 description: "Hex your enemy with repeated damage over time.".into(),
             },
             NeedlingHexAbility,
+            AbilityStats::new([
+                (AbilityStat::Damage, StatValue::Amount(5.0)),
+                (
+                    AbilityStat::TickInterval,
+                    StatValue::Duration(Duration::from_millis(500)),
+                ),
+                (AbilityStat::NumTicks, StatValue::Count(5)),
+            ]),
             AbilitySlotRequirement(AbilitySlotType::Magic),
             AbilityCooldown {
                 duration: THIS_ABILITY_ABILITY_COOLDOWN,
             },
+            OnGlobalCooldown,
             AbilityCastTime(Duration::ZERO),
+            AbilityCost {
+                resource: ResourceType::Mana,
+                amount: 40.0,
+                payment: CostPayment::OnCastFinish,
+            },
         ))
         .id()
 }
@@ -47,75 +63,58 @@ fn register_ability(catalog: Res<AbilityCatalog>) {
     catalog.register(THIS_ABILITY_ID, spawn_needling_hex);
 }
 
-#[derive(Debug, Component, Reflect, Deref, DerefMut)]
+#[derive(Debug, Component, Reflect)]
 #[reflect(GameEffect)]
-pub struct NeedlingHexEffect(FiniteRepeatingTimer);
+pub struct NeedlingHexEffect {
+    pub dmg_per_tick: f64,
+}
 
 impl GameEffect for NeedlingHexEffect {}
 
-impl NeedlingHexEffect {
-    pub const TICK_INTERVAL: Duration = Duration::from_millis(500);
-    pub const NUM_TICKS: u32 = 5;
+impl StackableEffect for NeedlingHexEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::AddStack { max_stacks: 3 };
+    const CLASSIFICATION: EffectClassification =
+        EffectClassification::debuff(EffectCategory::Magic);
+}
 
-    pub const DMG_PER_TICK: f64 = 5.0;
+impl EffectFromStats for NeedlingHexEffect {
+    fn from_stats(stats: &AbilityStats) -> Option<(Self, EffectTimer)> {
+        let tick_interval = stats.duration(AbilityStat::TickInterval)?;
+        let num_ticks = stats.count(AbilityStat::NumTicks)?;
 
-    fn new() -> NeedlingHexEffect {
-        NeedlingHexEffect(FiniteRepeatingTimer::new(
-            Self::TICK_INTERVAL,
-            Self::NUM_TICKS,
+        Some((
+            NeedlingHexEffect {
+                dmg_per_tick: stats.amount(AbilityStat::Damage)?,
+            },
+            EffectTimer::periodic(tick_interval, num_ticks),
         ))
     }
 }
 
-fn on_needling_hex(
-    trigger: On<PerformAbility>,
-    mut effects_interface: UniqueEffectInterface<NeedlingHexEffect>,
-    abilities: Query<(), With<NeedlingHexAbility>>,
+impl EffectApplyingAbility for NeedlingHexAbility {
+    type Effect = NeedlingHexEffect;
+    const TARGET: EffectTarget = EffectTarget::Target;
+}
+
+fn on_needling_hex_ticked(
+    trigger: On<EffectTicked>,
+    effects: Query<(&NeedlingHexEffect, &EffectStacks, &EffectSource)>,
+    mut deal_damage_events: MessageWriter<DealDamage>,
 ) {
     let event = trigger.event();
 
-    let Ok(_ability_e) = abilities.get(event.ability_entity) else {
-        return;
-    };
-
-    // Needling Hex needs a target.
-    let Some(target_e) = event.target else {
-        error!("Needling Hex without target - ignoring. Event: {event:?}");
+    let Ok((effect, stacks, source)) = effects.get(event.effect_entity) else {
         return;
     };
 
-    // Apply effect
-    effects_interface.spawn_or_replace_unique_effect(target_e, NeedlingHexEffect::new());
-}
-
-fn tick_needling_hex_effects(
-    mut effects: Query<(Entity, &mut NeedlingHexEffect)>,
-    mut effects_interface: UniqueEffectInterface<NeedlingHexEffect>,
-    mut deal_damage_events: MessageWriter<DealDamage>,
-    fight_interface: FightInterface,
-    time: Res<Time>,
-) {
-    for (effect_e, mut effect) in &mut effects {
-        let effect_target = effects_interface.get_target_of_effect(effect_e);
-        let fight_e = fight_interface.get_fight_of_entity(effect_target);
-
-        if fight_interface.is_fight_paused(fight_e) {
-            continue;
-        }
-
-        let just_elapsed_ticks = effect.tick_get_fresh_ticks(time.delta());
-
-        if effect.is_finished() {
-            effects_interface.remove_unique_effect(effect_target);
-        }
-
-        for _ in 0..just_elapsed_ticks {
-            deal_damage_events.write(DealDamage(DamageInstance {
-                source: None,
-                target: effect_target,
-                amount: NeedlingHexEffect::DMG_PER_TICK,
-            }));
-        }
+    for _ in 0..event.ticks {
+        deal_damage_events.write(DealDamage(DamageInstance {
+            source: source.caster,
+            source_ability: source.ability,
+            target: event.target,
+            amount: effect.dmg_per_tick * f64::from(stacks.count()),
+            damage_type: DamageType::Magical,
+        }));
     }
 }
 
@@ -127,10 +126,7 @@ impl Plugin for NeedlingHexPlugin {
         app.register_type::<NeedlingHexEffect>()
             .register_type::<NeedlingHexAbility>()
             .add_systems(PreStartup, register_ability)
-            .add_systems(
-                FixedUpdate,
-                tick_needling_hex_effects.in_set(PerUpdateSet::LogicUpdate),
-            )
-            .add_observer(on_needling_hex);
+            .add_observer(apply_ability_effect::<NeedlingHexAbility>)
+            .add_observer(on_needling_hex_ticked);
     }
 }
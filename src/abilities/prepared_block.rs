@@ -1,25 +1,23 @@
-// TODO: probably use needling_hex.rs as a basis for this spell
-//
-// Intended function: Cast time ~1s, -> Channel 3-5s, block 1 attack, up to X dmg
+// Intended function: Cast time ~1s, -> block the next attack within a few seconds, up to X dmg
 
 use std::time::Duration;
 
-use bevy::prelude::*;
+use bevy::{platform::collections::HashSet, prelude::*};
 
-use super::AbilityCatalog;
-use crate::{
-    PerUpdateSet,
-    game_logic::{
-        ability::{
-            Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
-            PerformAbility,
-        },
-        ability_slots::AbilitySlotType,
-        damage_resolution::{DamageInstance, DealDamage},
-        effects::{GameEffect, ReflectGameEffect, UniqueEffectInterface},
-        fight::FightInterface,
+use super::{
+    AbilityCatalog, EffectApplyingAbility, apply_ability_effect, definitions::EffectTarget,
+};
+use crate::game_logic::{
+    ability::{Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement},
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStats, StatValue},
+    absorb_shield::absorb_damage_with_shields,
+    cooldown::{CooldownStart, CooldownTiming},
+    damage_resolution::{AbsorbedDamage, DamageInstance, DamageResolutionStep},
+    effects::{
+        EffectCategory, EffectClassification, EffectFromStats, EffectInterface, EffectTimer,
+        GameEffect, ReflectGameEffect, StackableEffect, StackingPolicy,
     },
-    utils::FiniteRepeatingTimer,
 };
 
 // Marker component for Prepared Block ability
@@ -29,19 +27,31 @@ pub struct PreparedBlockAbility;
 const THIS_ABILITY_ID: AbilityId = AbilityId::PreparedBlock;
 const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(30);
 
-fn spawn_prepared_block(commands: &mut Commands) -> Entity {
+pub fn spawn_prepared_block(commands: &mut Commands) -> Entity {
     commands
         .spawn((
             Ability {
                 id: THIS_ABILITY_ID,
                 name: "Prepared Block".into(),
-                description: "Prepare to block the next hit you would take (up to a certain amount of damage).".into(),
+                description: "Prepare to block the next hit you would take within {EffectDuration}, absorbing up to {AbsorbAmount} damage.".into(),
             },
             PreparedBlockAbility,
+            AbilityStats::new([
+                (AbilityStat::AbsorbAmount, StatValue::Amount(20.0)),
+                (
+                    AbilityStat::EffectDuration,
+                    StatValue::Duration(Duration::from_secs(4)),
+                ),
+            ]),
             AbilitySlotRequirement(AbilitySlotType::ShieldDefend),
             AbilityCooldown {
                 duration: THIS_ABILITY_ABILITY_COOLDOWN,
             },
+            // the cooldown starts once the block was used up or expired
+            CooldownTiming {
+                start: CooldownStart::EffectExpiry,
+                ..default()
+            },
             AbilityCastTime(Duration::from_secs(1)),
         ))
         .id()
@@ -51,75 +61,63 @@ fn register_ability(catalog: Res<AbilityCatalog>) {
     catalog.register(THIS_ABILITY_ID, spawn_prepared_block);
 }
 
-#[derive(Debug, Component, Reflect, Deref, DerefMut)]
+/// Absorbs up to `absorb_amount` of the next hit the holder takes, and is consumed by it. Expires
+/// if no hit is taken until its [`EffectTimer`] finishes.
+#[derive(Debug, Component, Reflect)]
 #[reflect(GameEffect)]
-pub struct PreparedBlockEffect(FiniteRepeatingTimer);
+pub struct PreparedBlockEffect {
+    pub absorb_amount: f64,
+}
 
 impl GameEffect for PreparedBlockEffect {}
 
-impl PreparedBlockEffect {
-    pub const TICK_INTERVAL: Duration = Duration::from_millis(500);
-    pub const NUM_TICKS: u32 = 5;
-
-    pub const DMG_PER_TICK: f64 = 5.0;
+impl StackableEffect for PreparedBlockEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::Replace;
+    const CLASSIFICATION: EffectClassification =
+        EffectClassification::buff(EffectCategory::Physical).undispellable();
+}
 
-    fn new() -> PreparedBlockEffect {
-        PreparedBlockEffect(FiniteRepeatingTimer::new(
-            Self::TICK_INTERVAL,
-            Self::NUM_TICKS,
+impl EffectFromStats for PreparedBlockEffect {
+    fn from_stats(stats: &AbilityStats) -> Option<(Self, EffectTimer)> {
+        Some((
+            PreparedBlockEffect {
+                absorb_amount: stats.amount(AbilityStat::AbsorbAmount)?,
+            },
+            EffectTimer::duration(stats.duration(AbilityStat::EffectDuration)?),
         ))
     }
 }
 
-fn on_prepared_block(
-    trigger: On<PerformAbility>,
-    mut effects_interface: UniqueEffectInterface<PreparedBlockEffect>,
-    abilities: Query<(), With<PreparedBlockAbility>>,
-) {
-    let event = trigger.event();
-
-    let Ok(_ability_e) = abilities.get(event.ability_entity) else {
-        return;
-    };
-
-    // Prepared Block requires a caster.
-    let Some(caster_e) = event.caster else {
-        error!("PreparedBlock without caster - ignoring. Event: {event:?}");
-        return;
-    };
-
-    // Apply effect
-    effects_interface.spawn_or_replace_unique_effect(caster_e, PreparedBlockEffect::new());
+impl EffectApplyingAbility for PreparedBlockAbility {
+    type Effect = PreparedBlockEffect;
+    const TARGET: EffectTarget = EffectTarget::Caster;
 }
 
-fn tick_prepared_block_effects(
-    mut effects: Query<(Entity, &mut PreparedBlockEffect)>,
-    mut effects_interface: UniqueEffectInterface<PreparedBlockEffect>,
-    mut deal_damage_events: MessageWriter<DealDamage>,
-    fight_interface: FightInterface,
-    time: Res<Time>,
+/// Reduces the first hit on each holder of a [`PreparedBlockEffect`], and consumes the effect.
+fn absorb_damage_with_prepared_block(
+    mut damage_instances: Query<(&mut DamageInstance, &mut AbsorbedDamage)>,
+    effects: Query<&PreparedBlockEffect>,
+    mut effects_interface: EffectInterface<PreparedBlockEffect>,
 ) {
-    for (effect_e, mut effect) in &mut effects {
-        let effect_target = effects_interface.get_target_of_effect(effect_e);
-        let fight_e = fight_interface.get_fight_of_entity(effect_target);
+    let mut consumed_on = HashSet::new();
 
-        if fight_interface.is_fight_paused(fight_e) {
+    for (mut damage, mut absorbed) in &mut damage_instances {
+        // the effect is only despawned after this system, so track consumption here
+        if consumed_on.contains(&damage.target) {
             continue;
         }
 
-        let just_elapsed_ticks = effect.tick_get_fresh_ticks(time.delta());
+        let Some((effect_e, effect)) = effects_interface
+            .get_effect(damage.target)
+            .and_then(|effect_e| effects.get(effect_e).ok().map(|effect| (effect_e, effect)))
+        else {
+            continue;
+        };
 
-        if effect.is_finished() {
-            effects_interface.remove_unique_effect(effect_target);
-        }
+        absorbed.absorb(&mut damage, effect.absorb_amount);
 
-        for _ in 0..just_elapsed_ticks {
-            deal_damage_events.write(DealDamage(DamageInstance {
-                source: None,
-                target: effect_target,
-                amount: PreparedBlockEffect::DMG_PER_TICK,
-            }));
-        }
+        effects_interface.remove_effect(effect_e);
+        consumed_on.insert(damage.target);
     }
 }
 
@@ -132,9 +130,95 @@ impl Plugin for PreparedBlockPlugin {
             .register_type::<PreparedBlockAbility>()
             .add_systems(PreStartup, register_ability)
             .add_systems(
-                FixedUpdate,
-                tick_prepared_block_effects.in_set(PerUpdateSet::LogicUpdate),
+                Update,
+                absorb_damage_with_prepared_block
+                    .in_set(DamageResolutionStep::Absorb)
+                    .before(absorb_damage_with_shields),
             )
-            .add_observer(on_prepared_block);
+            .add_observer(apply_ability_effect::<PreparedBlockAbility>);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+    use super::{PreparedBlockEffect, PreparedBlockPlugin};
+    use crate::{
+        abilities::AbilityCatalog,
+        game_logic::{
+            commands::CommandsPlugin,
+            damage_resolution::{DamageInstance, DamageResolutionPlugin, DamageType, DealDamage},
+            effects::{EffectInterface, EffectSource, EffectTimer, EffectsPlugin, HasEffects},
+            fight::FightPlugin,
+            health::Health,
+        },
+        test_utils::{TestFightEntities, spawn_test_fight},
+    };
+
+    fn hit(app: &mut App, target: Entity, amount: f64) {
+        app.world_mut().write_message(DealDamage(DamageInstance {
+            source: None,
+            source_ability: None,
+            target,
+            amount,
+            damage_type: DamageType::Physical,
+        }));
+    }
+
+    #[test]
+    fn test_prepared_block_absorbs_next_hit_only() {
+        let mut app = App::new();
+        app.init_resource::<AbilityCatalog>()
+            .add_plugins(MinimalPlugins)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(DamageResolutionPlugin)
+            .add_plugins(PreparedBlockPlugin);
+
+        let TestFightEntities {
+            fight_e: _,
+            caster_e,
+            slot_e: _,
+            ability_e: _,
+            enemy_e: _,
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .run_system_once(
+                move |mut effects_interface: EffectInterface<PreparedBlockEffect>| {
+                    effects_interface.apply_timed_effect(
+                        caster_e,
+                        EffectSource::default(),
+                        PreparedBlockEffect {
+                            absorb_amount: 20.0,
+                        },
+                        EffectTimer::duration(Duration::from_secs(4)),
+                    );
+                },
+            )
+            .unwrap();
+
+        // both hits arrive in the same update, only one of them is blocked
+        hit(&mut app, caster_e, 15.0);
+        hit(&mut app, caster_e, 15.0);
+        app.update();
+
+        assert_eq!(app.world().get::<Health>(caster_e).unwrap().current(), 85.0);
+
+        assert!(
+            app.world()
+                .get::<HasEffects>(caster_e)
+                .is_none_or(|has_effects| has_effects.effects().is_empty()),
+            "Prepared Block should be consumed by the hit"
+        );
+
+        hit(&mut app, caster_e, 15.0);
+        app.update();
+
+        assert_eq!(app.world().get::<Health>(caster_e).unwrap().current(), 70.0);
     }
 }
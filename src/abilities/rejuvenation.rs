@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{
+    AbilityCatalog, EffectApplyingAbility, apply_ability_effect, definitions::EffectTarget,
+};
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
+        OnGlobalCooldown,
+    },
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStats, StatValue},
+    effects::{
+        EffectCategory, EffectClassification, EffectFromStats, EffectSource, EffectTicked,
+        EffectTimer, GameEffect, ReflectGameEffect, StackableEffect, StackingPolicy,
+    },
+    healing::{Heal, HealInstance},
+    resource_pools::{AbilityCost, CostPayment, ResourceType},
+};
+
+// Marker component for rejuvenation ability
+#[derive(Component, Debug, Reflect)]
+pub struct RejuvenationAbility;
+
+const THIS_ABILITY_ID: AbilityId = AbilityId::Rejuvenation;
+const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(15);
+
+pub fn spawn_rejuvenation(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Ability {
+                id: THIS_ABILITY_ID,
+                name: "Rejuvenation".into(),
+                description: "Heal yourself for {Healing} health every {TickInterval}.".into(),
+            },
+            RejuvenationAbility,
+            AbilityStats::new([
+                (AbilityStat::Healing, StatValue::Amount(4.0)),
+                (
+                    AbilityStat::TickInterval,
+                    StatValue::Duration(Duration::from_secs(1)),
+                ),
+                (AbilityStat::NumTicks, StatValue::Count(5)),
+            ]),
+            AbilitySlotRequirement(AbilitySlotType::Magic),
+            AbilityCooldown {
+                duration: THIS_ABILITY_ABILITY_COOLDOWN,
+            },
+            OnGlobalCooldown,
+            AbilityCastTime(Duration::from_secs(1)),
+            AbilityCost {
+                resource: ResourceType::Mana,
+                amount: 25.0,
+                payment: CostPayment::OnCastFinish,
+            },
+        ))
+        .id()
+}
+
+fn register_ability(catalog: Res<AbilityCatalog>) {
+    catalog.register(THIS_ABILITY_ID, spawn_rejuvenation);
+}
+
+#[derive(Debug, Component, Reflect)]
+#[reflect(GameEffect)]
+pub struct RejuvenationEffect {
+    pub heal_per_tick: f64,
+}
+
+impl GameEffect for RejuvenationEffect {}
+
+impl StackableEffect for RejuvenationEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::ExtendDuration;
+    const CLASSIFICATION: EffectClassification = EffectClassification::buff(EffectCategory::Magic);
+}
+
+impl EffectFromStats for RejuvenationEffect {
+    fn from_stats(stats: &AbilityStats) -> Option<(Self, EffectTimer)> {
+        let tick_interval = stats.duration(AbilityStat::TickInterval)?;
+        let num_ticks = stats.count(AbilityStat::NumTicks)?;
+
+        Some((
+            RejuvenationEffect {
+                heal_per_tick: stats.amount(AbilityStat::Healing)?,
+            },
+            EffectTimer::periodic(tick_interval, num_ticks),
+        ))
+    }
+}
+
+impl EffectApplyingAbility for RejuvenationAbility {
+    type Effect = RejuvenationEffect;
+    const TARGET: EffectTarget = EffectTarget::Caster;
+}
+
+fn on_rejuvenation_ticked(
+    trigger: On<EffectTicked>,
+    effects: Query<(&RejuvenationEffect, &EffectSource)>,
+    mut heal_events: MessageWriter<Heal>,
+) {
+    let event = trigger.event();
+
+    let Ok((effect, source)) = effects.get(event.effect_entity) else {
+        return;
+    };
+
+    for _ in 0..event.ticks {
+        heal_events.write(Heal(HealInstance {
+            source: source.caster,
+            source_ability: source.ability,
+            target: event.target,
+            amount: effect.heal_per_tick,
+            resurrect: false,
+        }));
+    }
+}
+
+#[derive(Debug)]
+pub struct RejuvenationPlugin;
+
+impl Plugin for RejuvenationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RejuvenationEffect>()
+            .register_type::<RejuvenationAbility>()
+            .add_systems(PreStartup, register_ability)
+            .add_observer(apply_ability_effect::<RejuvenationAbility>)
+            .add_observer(on_rejuvenation_ticked);
+    }
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{AbilityCatalog, CONTROL_COOLDOWN_GROUP};
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityCooldownGroup, AbilityId,
+        AbilitySlotRequirement, OnGlobalCooldown, PerformAbility,
+    },
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStats, AbilityStatsInterface, StatValue},
+    crowd_control::StunEffect,
+    effects::{EffectInterface, EffectSource, EffectTimer},
+    resource_pools::{AbilityCost, CostPayment, ResourceType},
+};
+
+// Marker component for shield bash ability
+#[derive(Component, Debug, Reflect)]
+pub struct ShieldBashAbility;
+
+const THIS_ABILITY_ID: AbilityId = AbilityId::ShieldBash;
+const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(20);
+const THIS_ABILITY_GROUP_COOLDOWN: Duration = Duration::from_secs(5);
+
+pub fn spawn_shield_bash(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Ability {
+                id: THIS_ABILITY_ID,
+                name: "Shield Bash".into(),
+                description:
+                    "Bash your enemy with your shield, stunning them for {EffectDuration}.".into(),
+            },
+            ShieldBashAbility,
+            AbilityStats::new([(
+                AbilityStat::EffectDuration,
+                StatValue::Duration(Duration::from_secs(2)),
+            )]),
+            AbilitySlotRequirement(AbilitySlotType::ShieldDefend),
+            AbilityCooldown {
+                duration: THIS_ABILITY_ABILITY_COOLDOWN,
+            },
+            AbilityCooldownGroup {
+                group: CONTROL_COOLDOWN_GROUP,
+                duration: THIS_ABILITY_GROUP_COOLDOWN,
+            },
+            OnGlobalCooldown,
+            AbilityCastTime(Duration::ZERO),
+            AbilityCost {
+                resource: ResourceType::Stamina,
+                amount: 20.0,
+                payment: CostPayment::OnCastFinish,
+            },
+        ))
+        .id()
+}
+
+fn register_ability(catalog: Res<AbilityCatalog>) {
+    catalog.register(THIS_ABILITY_ID, spawn_shield_bash);
+}
+
+fn on_shield_bash(
+    trigger: On<PerformAbility>,
+    mut effects_interface: EffectInterface<StunEffect>,
+    abilities: Query<(), With<ShieldBashAbility>>,
+    ability_stats: AbilityStatsInterface,
+) {
+    let event = trigger.event();
+
+    let Ok(_ability_e) = abilities.get(event.ability_entity) else {
+        return;
+    };
+
+    let Some(target_e) = event.target else {
+        error!("Shield Bash without target - ignoring. Event: {event:?}");
+        return;
+    };
+
+    let stats = ability_stats.resolved_stats(event.ability_entity);
+    let Some(duration) = stats.duration(AbilityStat::EffectDuration) else {
+        error!("Shield Bash with incomplete stats {stats:?} - ignoring. Event: {event:?}");
+        return;
+    };
+
+    effects_interface.apply_timed_effect(
+        target_e,
+        EffectSource::from(event),
+        StunEffect,
+        EffectTimer::duration(duration),
+    );
+}
+
+#[derive(Debug)]
+pub struct ShieldBashPlugin;
+
+impl Plugin for ShieldBashPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ShieldBashAbility>()
+            .add_systems(PreStartup, register_ability)
+            .add_observer(on_shield_bash);
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{
+    AbilityCatalog, EffectApplyingAbility, apply_ability_effect, definitions::EffectTarget,
+};
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
+        OnGlobalCooldown,
+    },
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStats, StatValue},
+    cooldown::AbilityCharges,
+    damage_resolution::{DamageInstance, DamageType, DealDamage},
+    effects::{
+        DelayedDetonation, EffectCategory, EffectClassification, EffectDetonated, EffectFromStats,
+        EffectTimer, GameEffect, ReflectGameEffect, StackableEffect, StackingPolicy,
+    },
+    resource_pools::{AbilityCost, CostPayment, ResourceType},
+};
+
+// Marker component for volatile charge ability
+#[derive(Component, Debug, Reflect)]
+pub struct VolatileChargeAbility;
+
+const THIS_ABILITY_ID: AbilityId = AbilityId::VolatileCharge;
+const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(2);
+const THIS_ABILITY_MAX_CHARGES: u32 = 2;
+const THIS_ABILITY_RECHARGE_DURATION: Duration = Duration::from_secs(15);
+
+pub fn spawn_volatile_charge(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Ability {
+                id: THIS_ABILITY_ID,
+                name: "Volatile Charge".into(),
+                description: "Plant a volatile charge on your enemy that explodes after {EffectDuration}, dealing {Damage} damage. Dispelling it defuses the charge.".into(),
+            },
+            VolatileChargeAbility,
+            AbilityStats::new([
+                (AbilityStat::Damage, StatValue::Amount(40.0)),
+                (
+                    AbilityStat::EffectDuration,
+                    StatValue::Duration(Duration::from_secs(5)),
+                ),
+            ]),
+            AbilitySlotRequirement(AbilitySlotType::Magic),
+            AbilityCooldown {
+                duration: THIS_ABILITY_ABILITY_COOLDOWN,
+            },
+            OnGlobalCooldown,
+            AbilityCharges::new(THIS_ABILITY_MAX_CHARGES, THIS_ABILITY_RECHARGE_DURATION),
+            AbilityCastTime(Duration::from_secs(1)),
+            AbilityCost {
+                resource: ResourceType::Mana,
+                amount: 25.0,
+                payment: CostPayment::OnCastFinish,
+            },
+        ))
+        .id()
+}
+
+fn register_ability(catalog: Res<AbilityCatalog>) {
+    catalog.register(THIS_ABILITY_ID, spawn_volatile_charge);
+}
+
+/// Deals `damage` to its holder when it detonates.
+#[derive(Debug, Component, Reflect)]
+#[reflect(GameEffect)]
+pub struct VolatileChargeEffect {
+    pub damage: f64,
+}
+
+impl GameEffect for VolatileChargeEffect {}
+
+impl StackableEffect for VolatileChargeEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::IndependentPerSource;
+    const CLASSIFICATION: EffectClassification =
+        EffectClassification::debuff(EffectCategory::Magic);
+    const DETONATION: Option<DelayedDetonation> = Some(DelayedDetonation {
+        detonate_on_dispel: false,
+    });
+}
+
+impl EffectFromStats for VolatileChargeEffect {
+    fn from_stats(stats: &AbilityStats) -> Option<(Self, EffectTimer)> {
+        Some((
+            VolatileChargeEffect {
+                damage: stats.amount(AbilityStat::Damage)?,
+            },
+            EffectTimer::duration(stats.duration(AbilityStat::EffectDuration)?),
+        ))
+    }
+}
+
+impl EffectApplyingAbility for VolatileChargeAbility {
+    type Effect = VolatileChargeEffect;
+    const TARGET: EffectTarget = EffectTarget::Target;
+}
+
+fn on_volatile_charge_detonated(
+    trigger: On<EffectDetonated>,
+    effects: Query<&VolatileChargeEffect>,
+    mut deal_damage_events: MessageWriter<DealDamage>,
+) {
+    let event = trigger.event();
+
+    let Ok(effect) = effects.get(event.effect_entity) else {
+        return;
+    };
+
+    deal_damage_events.write(DealDamage(DamageInstance {
+        source: event.source.caster,
+        source_ability: event.source.ability,
+        target: event.target,
+        amount: effect.damage,
+        damage_type: DamageType::Magical,
+    }));
+}
+
+#[derive(Debug)]
+pub struct VolatileChargePlugin;
+
+impl Plugin for VolatileChargePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<VolatileChargeEffect>()
+            .register_type::<VolatileChargeAbility>()
+            .add_systems(PreStartup, register_ability)
+            .add_observer(apply_ability_effect::<VolatileChargeAbility>)
+            .add_observer(on_volatile_charge_detonated);
+    }
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{
+    AbilityCatalog, EffectApplyingAbility, apply_ability_effect, definitions::EffectTarget,
+};
+use crate::game_logic::{
+    ability::{
+        Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
+        OnGlobalCooldown,
+    },
+    ability_slots::AbilitySlotType,
+    ability_stats::{AbilityStat, AbilityStats, StatModifierKind, StatValue},
+    character_stats::{
+        CharacterStat, CharacterStatModifier, StatModifyingEffect, insert_stat_modifiers,
+    },
+    effects::{
+        EffectCategory, EffectClassification, EffectFromStats, EffectTimer, GameEffect,
+        ReflectGameEffect, StackableEffect, StackingPolicy,
+    },
+    resource_pools::{AbilityCost, CostPayment, ResourceType},
+};
+
+// Marker component for weaken ability
+#[derive(Component, Debug, Reflect)]
+pub struct WeakenAbility;
+
+const THIS_ABILITY_ID: AbilityId = AbilityId::Weaken;
+const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(20);
+
+pub fn spawn_weaken(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Ability {
+                id: THIS_ABILITY_ID,
+                name: "Weaken".into(),
+                description: "Weaken your enemy, reducing the damage they deal to {OutgoingDamageMultiplier} for {EffectDuration}.".into(),
+            },
+            WeakenAbility,
+            AbilityStats::new([
+                (
+                    AbilityStat::EffectDuration,
+                    StatValue::Duration(Duration::from_secs(8)),
+                ),
+                (
+                    AbilityStat::OutgoingDamageMultiplier,
+                    StatValue::Multiplier(0.7),
+                ),
+            ]),
+            AbilitySlotRequirement(AbilitySlotType::Magic),
+            AbilityCooldown {
+                duration: THIS_ABILITY_ABILITY_COOLDOWN,
+            },
+            OnGlobalCooldown,
+            AbilityCastTime(Duration::from_secs(1)),
+            AbilityCost {
+                resource: ResourceType::Mana,
+                amount: 25.0,
+                payment: CostPayment::OnCastFinish,
+            },
+        ))
+        .id()
+}
+
+fn register_ability(catalog: Res<AbilityCatalog>) {
+    catalog.register(THIS_ABILITY_ID, spawn_weaken);
+}
+
+/// Reduces the damage dealt by its holder.
+#[derive(Debug, Component, Reflect)]
+#[reflect(GameEffect)]
+pub struct WeakenEffect {
+    pub outgoing_damage_multiplier: f64,
+}
+
+impl GameEffect for WeakenEffect {}
+
+impl StackableEffect for WeakenEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::RefreshDuration;
+    const CLASSIFICATION: EffectClassification =
+        EffectClassification::debuff(EffectCategory::Curse).with_diminishing_returns();
+}
+
+impl StatModifyingEffect for WeakenEffect {
+    fn stat_modifiers(&self) -> Vec<CharacterStatModifier> {
+        vec![CharacterStatModifier {
+            stat: CharacterStat::OutgoingDamage,
+            kind: StatModifierKind::Multiply(self.outgoing_damage_multiplier),
+        }]
+    }
+}
+
+impl EffectFromStats for WeakenEffect {
+    fn from_stats(stats: &AbilityStats) -> Option<(Self, EffectTimer)> {
+        Some((
+            WeakenEffect {
+                outgoing_damage_multiplier: stats
+                    .multiplier(AbilityStat::OutgoingDamageMultiplier)?,
+            },
+            EffectTimer::duration(stats.duration(AbilityStat::EffectDuration)?),
+        ))
+    }
+}
+
+impl EffectApplyingAbility for WeakenAbility {
+    type Effect = WeakenEffect;
+    const TARGET: EffectTarget = EffectTarget::Target;
+}
+
+#[derive(Debug)]
+pub struct WeakenPlugin;
+
+impl Plugin for WeakenPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<WeakenEffect>()
+            .register_type::<WeakenAbility>()
+            .add_systems(PreStartup, register_ability)
+            .add_observer(apply_ability_effect::<WeakenAbility>)
+            .add_observer(insert_stat_modifiers::<WeakenEffect>);
+    }
+}
@@ -10,7 +10,8 @@ use crate::{
             PerformAbility,
         },
         ability_slots::AbilitySlotType,
-        damage_resolution::{DamageInstance, DealDamage},
+        ability_stats::{AbilityStat, AbilityStats, AbilityStatsInterface, StatValue},
+        damage_resolution::{DamageInstance, DamageType, DealDamage},
     },
     utils::holds_held::Held,
 };
@@ -20,7 +21,6 @@ use crate::{
 pub struct WeaponAttackAbility;
 
 const THIS_ABILITY_ID: AbilityId = AbilityId::WeaponAttack;
-const THIS_ABILITY_DAMAGE: f64 = 10.0;
 const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(5);
 
 pub fn spawn_weapon_attack(commands: &mut Commands) -> Entity {
@@ -29,12 +29,10 @@ pub fn spawn_weapon_attack(commands: &mut Commands) -> Entity {
             Ability {
                 id: THIS_ABILITY_ID,
                 name: "Attack".into(),
-                description: format!(
-                    "Strike with your weapon, dealing {THIS_ABILITY_DAMAGE} damage."
-                )
-                .into(),
+                description: "Strike with your weapon, dealing {Damage} damage.".into(),
             },
             WeaponAttackAbility,
+            AbilityStats::new([(AbilityStat::Damage, StatValue::Amount(10.0))]),
             AbilitySlotRequirement(AbilitySlotType::WeaponAttack),
             AbilityCooldown {
                 duration: THIS_ABILITY_ABILITY_COOLDOWN,
@@ -52,6 +50,7 @@ fn on_weapon_attack(
     trigger: On<PerformAbility>,
     mut deal_damage_events: MessageWriter<DealDamage>,
     abilities: Query<&Held<Ability>, With<WeaponAttackAbility>>,
+    ability_stats: AbilityStatsInterface,
 ) {
     let event = trigger.event();
 
@@ -69,11 +68,19 @@ fn on_weapon_attack(
         return;
     };
 
+    let stats = ability_stats.resolved_stats(event.ability_entity);
+    let Some(damage) = stats.amount(AbilityStat::Damage) else {
+        error!("Weapon Attack ability without Damage stat. Event: {event:?}");
+        return;
+    };
+
     // Deal damage
     deal_damage_events.write(DealDamage(DamageInstance {
         source: Some(caster_e),
+        source_ability: Some(event.ability_entity),
         target: target_e,
-        amount: THIS_ABILITY_DAMAGE,
+        amount: damage,
+        damage_type: DamageType::Physical,
     }));
 }
 
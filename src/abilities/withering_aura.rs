@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::AbilityCatalog;
+use crate::{
+    PerUpdateSet,
+    game_logic::{
+        ability::{
+            Ability, AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement,
+            PerformAbility,
+        },
+        ability_slots::AbilitySlotType,
+        ability_stats::{
+            AbilityStat, AbilityStats, AbilityStatsInterface, StatModifierKind, StatValue,
+        },
+        auras::{Aura, AuraTargets, sync_auras},
+        character_stats::{
+            CharacterStat, CharacterStatModifier, StatModifyingEffect, insert_stat_modifiers,
+        },
+        effects::{
+            EffectCategory, EffectClassification, GameEffect, ReflectGameEffect, StackableEffect,
+            StackingPolicy,
+        },
+    },
+};
+
+// Marker component for withering aura ability
+#[derive(Component, Debug, Reflect)]
+pub struct WitheringAuraAbility;
+
+const THIS_ABILITY_ID: AbilityId = AbilityId::WitheringAura;
+const THIS_ABILITY_ABILITY_COOLDOWN: Duration = Duration::from_secs(5);
+
+pub fn spawn_withering_aura(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Ability {
+                id: THIS_ABILITY_ID,
+                name: "Withering Aura".into(),
+                description: "Toggle an aura that reduces the damage dealt by all enemies to {OutgoingDamageMultiplier} while you are alive.".into(),
+            },
+            WitheringAuraAbility,
+            AbilityStats::new([(
+                AbilityStat::OutgoingDamageMultiplier,
+                StatValue::Multiplier(0.85),
+            )]),
+            AbilitySlotRequirement(AbilitySlotType::Magic),
+            AbilityCooldown {
+                duration: THIS_ABILITY_ABILITY_COOLDOWN,
+            },
+            AbilityCastTime(Duration::ZERO),
+        ))
+        .id()
+}
+
+fn register_ability(catalog: Res<AbilityCatalog>) {
+    catalog.register(THIS_ABILITY_ID, spawn_withering_aura);
+}
+
+/// Reduces the damage dealt by its holder, for as long as the aura applying it is active.
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(GameEffect)]
+pub struct WitheringAuraEffect {
+    pub outgoing_damage_multiplier: f64,
+}
+
+impl GameEffect for WitheringAuraEffect {}
+
+impl StackableEffect for WitheringAuraEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::IndependentPerSource;
+    // the aura would re-apply it immediately
+    const CLASSIFICATION: EffectClassification =
+        EffectClassification::debuff(EffectCategory::Curse).undispellable();
+}
+
+impl StatModifyingEffect for WitheringAuraEffect {
+    fn stat_modifiers(&self) -> Vec<CharacterStatModifier> {
+        vec![CharacterStatModifier {
+            stat: CharacterStat::OutgoingDamage,
+            kind: StatModifierKind::Multiply(self.outgoing_damage_multiplier),
+        }]
+    }
+}
+
+impl WitheringAuraEffect {
+    /// Creates the effect from the (resolved) stats of the ability applying it. Returns `None` if a
+    /// required stat is missing.
+    pub fn from_stats(stats: &AbilityStats) -> Option<WitheringAuraEffect> {
+        Some(WitheringAuraEffect {
+            outgoing_damage_multiplier: stats.multiplier(AbilityStat::OutgoingDamageMultiplier)?,
+        })
+    }
+}
+
+fn on_withering_aura(
+    trigger: On<PerformAbility>,
+    abilities: Query<(), With<WitheringAuraAbility>>,
+    auras: Query<(), With<Aura<WitheringAuraEffect>>>,
+    ability_stats: AbilityStatsInterface,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+
+    let Ok(_ability_e) = abilities.get(event.ability_entity) else {
+        return;
+    };
+
+    let Some(caster_e) = event.caster else {
+        error!("Withering Aura without caster - ignoring. Event: {event:?}");
+        return;
+    };
+
+    // Performing the ability again ends the aura.
+    if auras.contains(caster_e) {
+        commands
+            .entity(caster_e)
+            .remove::<Aura<WitheringAuraEffect>>();
+    } else {
+        let stats = ability_stats.resolved_stats(event.ability_entity);
+        let Some(effect) = WitheringAuraEffect::from_stats(&stats) else {
+            error!("Withering Aura with incomplete stats {stats:?} - ignoring. Event: {event:?}");
+            return;
+        };
+
+        commands.entity(caster_e).insert(Aura {
+            effect,
+            targets: AuraTargets::Enemies,
+            required_ability: Some(event.ability_entity),
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct WitheringAuraPlugin;
+
+impl Plugin for WitheringAuraPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<WitheringAuraEffect>()
+            .register_type::<WitheringAuraAbility>()
+            .add_systems(PreStartup, register_ability)
+            .add_systems(
+                FixedUpdate,
+                sync_auras::<WitheringAuraEffect>.in_set(PerUpdateSet::LogicUpdate),
+            )
+            .add_observer(on_withering_aura)
+            .add_observer(insert_stat_modifiers::<WitheringAuraEffect>);
+    }
+}
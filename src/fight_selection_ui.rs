@@ -13,9 +13,12 @@ use crate::{
         ability::{Ability, AbilityId},
         ability_slots::{AbilitySlot, AbilitySlotType},
         ai_behavior::{AttackPlayerAction, CanAttackPlayerScorer},
+        cooldown::GlobalCooldown,
+        damage_resolution::{DamageType, Resistances},
         faction::Faction,
         fight::{Fight, FightBundle},
         health::Health,
+        resource_pools::{ResourcePool, ResourcePools, ResourceType},
     },
     utils::holds_held::Held,
 };
@@ -50,6 +53,15 @@ pub fn spawn_basic_fight(mut commands: Commands, ability_catalog: Res<AbilityCat
         AbilityId::NeedlingHex,
         AbilityId::ChargedStrike,
         AbilityId::PreparedBlock,
+        AbilityId::Rejuvenation,
+        AbilityId::ShieldBash,
+        AbilityId::Kick,
+        AbilityId::Weaken,
+        AbilityId::Haste,
+        AbilityId::Cleanse,
+        AbilityId::Barrier,
+        AbilityId::WitheringAura,
+        AbilityId::VolatileCharge,
     ]
     .into_iter()
     .map(|ability_id| ability_catalog.spawn(ability_id, &mut commands))
@@ -58,7 +70,15 @@ pub fn spawn_basic_fight(mut commands: Commands, ability_catalog: Res<AbilityCat
     let player_character = commands
         .spawn((
             Health::new(100.0),
+            ResourcePools::new([
+                (ResourceType::Mana, ResourcePool::new(100.0, 2.0)),
+                (ResourceType::Stamina, ResourcePool::new(100.0, 10.0)),
+            ]),
+            Resistances::new([(DamageType::Physical, 0.2)]),
             Faction::Player,
+            GlobalCooldown {
+                duration: Duration::from_secs(1),
+            },
             Name::new("Player Character"),
         ))
         .with_related_entities::<Held<AbilitySlot>>(|commands| {
@@ -84,6 +104,7 @@ pub fn spawn_basic_fight(mut commands: Commands, ability_catalog: Res<AbilityCat
         .spawn((
             Name::new("The Enemy"),
             Health::new(100.0),
+            Resistances::new([(DamageType::Physical, 0.1), (DamageType::Magical, 0.25)]),
             Faction::Enemy,
             Thinker::build()
                 .picker(FirstToScore { threshold: 0.5 })
@@ -1,48 +1,240 @@
-use std::{fmt::Write as _, sync::LazyLock, time::Duration};
+use std::{fmt::Write as _, time::Duration};
 
 use bevy::prelude::*;
 use bevy_inspector_egui::egui::{self, Id, Ui};
+use itertools::Itertools;
 
-use crate::{abilities::needling_hex::NeedlingHexEffect, utils::SplitDuration};
+use crate::{
+    abilities::{
+        haste::HasteEffect, needling_hex::NeedlingHexEffect, prepared_block::PreparedBlockEffect,
+        rejuvenation::RejuvenationEffect, volatile_charge::VolatileChargeEffect,
+        weaken::WeakenEffect, withering_aura::WitheringAuraEffect,
+    },
+    game_logic::{
+        absorb_shield::AbsorbShieldEffect,
+        crowd_control::{
+            CrowdControl, CrowdControlEffect, DisarmEffect, SilenceEffect, StunEffect,
+        },
+        effects::EffectTimer,
+    },
+    utils::SplitDuration,
+};
 
 #[reflect_trait]
 pub trait RenderGameEffectImmediate {
-    fn render_to_ui(&self, ui: &mut Ui);
+    /// `timer` is the [`EffectTimer`] of the effect, if it has one.
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>);
 }
 
 impl RenderGameEffectImmediate for NeedlingHexEffect {
-    fn render_to_ui(&self, ui: &mut Ui) {
-        static DMG_DESCRIPTION: LazyLock<String> = LazyLock::new(|| {
-            format!(
-                "Deals {dmg_per_tick} damage every {tick_interval}s, a total of {num_ticks} times.",
-                dmg_per_tick = NeedlingHexEffect::DMG_PER_TICK,
-                tick_interval = NeedlingHexEffect::TICK_INTERVAL.as_secs_f64(),
-                num_ticks = NeedlingHexEffect::NUM_TICKS,
-            )
-        });
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>) {
+        render_effect(
+            self,
+            ui,
+            timer,
+            &format!(
+                "Needling Hex ({remaining_ticks})",
+                remaining_ticks = timer.and_then(EffectTimer::remaining_ticks).unwrap_or(0),
+            ),
+            "A maddening hex that causes you to repeatedly take damage.",
+            timer
+                .and_then(EffectTimer::tick_interval)
+                .map(|tick_interval| {
+                    format!(
+                        "Deals {dmg_per_tick} damage every {tick_interval}s per stack.",
+                        dmg_per_tick = self.dmg_per_tick,
+                        tick_interval = tick_interval.as_secs_f64(),
+                    )
+                }),
+        );
+    }
+}
+
+impl RenderGameEffectImmediate for PreparedBlockEffect {
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>) {
+        render_effect(
+            self,
+            ui,
+            timer,
+            "Prepared Block",
+            "Ready to block the next incoming hit.",
+            Some(format!(
+                "Absorbs up to {absorb_amount} damage of the next hit.",
+                absorb_amount = self.absorb_amount,
+            )),
+        );
+    }
+}
+
+impl RenderGameEffectImmediate for RejuvenationEffect {
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>) {
+        render_effect(
+            self,
+            ui,
+            timer,
+            &format!(
+                "Rejuvenation ({remaining_ticks})",
+                remaining_ticks = timer.and_then(EffectTimer::remaining_ticks).unwrap_or(0),
+            ),
+            "Soothing magic that repeatedly restores health.",
+            timer
+                .and_then(EffectTimer::tick_interval)
+                .map(|tick_interval| {
+                    format!(
+                        "Heals {heal_per_tick} health every {tick_interval}s.",
+                        heal_per_tick = self.heal_per_tick,
+                        tick_interval = tick_interval.as_secs_f64(),
+                    )
+                }),
+        );
+    }
+}
+
+impl RenderGameEffectImmediate for WeakenEffect {
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>) {
+        render_effect(
+            self,
+            ui,
+            timer,
+            "Weaken",
+            "A sapping curse that drains your strength.",
+            Some(format!(
+                "Damage dealt is multiplied by {multiplier}.",
+                multiplier = self.outgoing_damage_multiplier,
+            )),
+        );
+    }
+}
 
-        let label = ui.label(format!(
-            "{remaining_time} Needling Hex ({remaining_ticks})",
-            remaining_time = format_remaining_time(&self.remaining_time()),
-            remaining_ticks = self.remaining_ticks()
-        ));
-
-        if label.contains_pointer() {
-            egui::Tooltip::always_open(
-                ui.ctx().clone(),
-                ui.layer_id(),
-                Id::new("EffectTooltip").with(self as *const _),
-                label.rect.right_top(),
-            )
-            .show(|ui| {
-                ui.label("A maddening hex that causes you to repeatedly take damage.");
+impl RenderGameEffectImmediate for HasteEffect {
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>) {
+        render_effect(
+            self,
+            ui,
+            timer,
+            "Haste",
+            "Everything seems to move a little slower around you.",
+            Some(format!(
+                "Cast times and tick intervals are multiplied by {cast_time}, cooldowns recover {cooldown_rate}x as fast.",
+                cast_time = self.cast_time_multiplier,
+                cooldown_rate = self.cooldown_rate_multiplier,
+            )),
+        );
+    }
+}
+
+impl RenderGameEffectImmediate for WitheringAuraEffect {
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>) {
+        render_effect(
+            self,
+            ui,
+            timer,
+            "Withering Aura",
+            "A withering presence saps your strength, as long as its source lives.",
+            Some(format!(
+                "Damage dealt is multiplied by {multiplier}.",
+                multiplier = self.outgoing_damage_multiplier,
+            )),
+        );
+    }
+}
+
+impl RenderGameEffectImmediate for VolatileChargeEffect {
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>) {
+        render_effect(
+            self,
+            ui,
+            timer,
+            "Volatile Charge",
+            "A charge of unstable magic, ticking away.",
+            Some(format!(
+                "Explodes for {damage} damage when it runs out. Dispelling it defuses the charge.",
+                damage = self.damage,
+            )),
+        );
+    }
+}
+
+impl RenderGameEffectImmediate for AbsorbShieldEffect {
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>) {
+        render_effect(
+            self,
+            ui,
+            timer,
+            &format!("Shield ({remaining:.0})", remaining = self.remaining),
+            "A protective shield that soaks up incoming damage.",
+            Some(match &self.damage_types {
+                Some(damage_types) => format!(
+                    "Absorbs {remaining:.2} more {damage_types} damage.",
+                    remaining = self.remaining,
+                    damage_types = damage_types.iter().join("/"),
+                ),
+                None => format!(
+                    "Absorbs {remaining:.2} more damage.",
+                    remaining = self.remaining,
+                ),
+            }),
+        );
+    }
+}
+
+impl<E: CrowdControlEffect> RenderGameEffectImmediate for E {
+    fn render_to_ui(&self, ui: &mut Ui, timer: Option<&EffectTimer>) {
+        render_effect(
+            self,
+            ui,
+            timer,
+            &E::CROWD_CONTROL.to_string(),
+            match E::CROWD_CONTROL {
+                CrowdControl::Stun => "Can't use any abilities.",
+                CrowdControl::Silence => "Can't use Magic abilities.",
+                CrowdControl::Disarm => "Can't use Weapon Attack abilities.",
+            },
+            None,
+        );
+    }
+}
+
+/// Renders `effect` as its remaining time and `name`, with a tooltip of its `description`,
+/// followed by the `details` of this instance.
+fn render_effect<E>(
+    effect: &E,
+    ui: &mut Ui,
+    timer: Option<&EffectTimer>,
+    name: &str,
+    description: &str,
+    details: Option<String>,
+) {
+    let label = ui.label(format!(
+        "{remaining_time} {name}",
+        remaining_time = format_effect_remaining_time(timer),
+    ));
+
+    if label.contains_pointer() {
+        egui::Tooltip::always_open(
+            ui.ctx().clone(),
+            ui.layer_id(),
+            Id::new("EffectTooltip").with(effect as *const E),
+            label.rect.right_top(),
+        )
+        .show(|ui| {
+            ui.label(description);
+            if let Some(details) = details {
                 ui.label("");
-                ui.label(&*DMG_DESCRIPTION);
-            });
-        }
+                ui.label(details);
+            }
+        });
     }
 }
 
+/// Formats the remaining time of an effect, or `-` for effects without [`EffectTimer`].
+fn format_effect_remaining_time(timer: Option<&EffectTimer>) -> String {
+    timer.map_or_else(
+        || "-".to_string(),
+        |timer| format_remaining_time(&timer.remaining()),
+    )
+}
+
 pub fn format_remaining_time(remaining: &Duration) -> String {
     let SplitDuration {
         days,
@@ -75,6 +267,16 @@ pub struct RenderEffectsPlugin;
 
 impl Plugin for RenderEffectsPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type_data::<NeedlingHexEffect, ReflectRenderGameEffectImmediate>();
+        app.register_type_data::<NeedlingHexEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<PreparedBlockEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<RejuvenationEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<WeakenEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<HasteEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<WitheringAuraEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<VolatileChargeEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<AbsorbShieldEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<StunEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<SilenceEffect, ReflectRenderGameEffectImmediate>()
+            .register_type_data::<DisarmEffect, ReflectRenderGameEffectImmediate>();
     }
 }
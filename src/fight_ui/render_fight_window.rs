@@ -18,15 +18,22 @@ use crate::{
     abilities::AbilityInterface,
     game_logic::{
         ability::{Ability, AbilitySlotRequirement},
-        ability_casting::{AbilityCastingInterface, UseAbility},
+        ability_casting::{AbilityCastingInterface, CancelCast, UseAbility},
         ability_slots::{AbilitySlot, AbilitySlotType},
+        ability_stats::{AbilityStats, AbilityStatsInterface},
+        absorb_shield::AbsorbShieldInterface,
         commands::GameCommand,
-        cooldown::Cooldown,
-        effects::{HasEffects, ReflectGameEffect},
+        cooldown::{AbilityCharges, Cooldown},
+        damage_resolution::Resistances,
+        effects::{
+            EffectClassification, EffectDisposition, EffectStacks, EffectTimer, HasEffects,
+            ReflectGameEffect,
+        },
         faction::Faction,
         fight::{Fight, FightInterface, FightResult, FightTime},
         health::Health,
         ongoing_cast::OngoingCastInterface,
+        resource_pools::{AbilityCost, ResourcePools},
     },
     utils::{SplitDuration, egui_systems::run_ui_system, holds_held::Holds},
 };
@@ -279,6 +286,10 @@ impl AbilitySlotsSectionUiState {
     }
 }
 
+#[expect(
+    clippy::too_many_arguments,
+    reason = "QueryStates as arguments are part of the ui-pattern (for now)"
+)]
 fn ui_fight_column(
     In((mut ui, (mut ui_column_state, model_e, target_e, fight_e))): In<(
         Ui,
@@ -287,9 +298,12 @@ fn ui_fight_column(
     world: &mut World,
     names: &mut QueryState<&Name>,
     healths: &mut QueryState<&Health>,
+    resource_pools: &mut QueryState<&ResourcePools>,
+    resistances: &mut QueryState<&Resistances>,
     holds_ability_slots: &mut QueryState<&Holds<AbilitySlot>>,
     holds_abilities: &mut QueryState<&Holds<Ability>>,
     has_effects: &mut QueryState<&HasEffects>,
+    absorb_shields: &mut SystemState<AbsorbShieldInterface>,
 ) -> (Ui, FightColumnUiState) {
     ui.indent(ui.id().with("entity_overview_section"), |ui: &mut Ui| {
         if let Ok(name) = names.get(world, model_e) {
@@ -298,15 +312,47 @@ fn ui_fight_column(
             ui.label("<No Name>");
         }
 
+        let shield = absorb_shields.get_mut(world).total_shield(model_e);
+        absorb_shields.apply(world);
+
         if let Ok(health) = healths.get(world, model_e) {
-            ui.label(format!(
-                "Health: {:.2}/{:.2}",
-                health.current(),
-                health.max()
-            ));
+            if shield > 0.0 {
+                ui.label(format!(
+                    "Health: {:.2}/{:.2} (+{shield:.2} Shield)",
+                    health.current(),
+                    health.max()
+                ));
+            } else {
+                ui.label(format!(
+                    "Health: {:.2}/{:.2}",
+                    health.current(),
+                    health.max()
+                ));
+            }
         } else {
             ui.label("<No Health>");
         }
+
+        if let Ok(pools) = resource_pools.get(world, model_e) {
+            for (resource, pool) in pools.iter() {
+                ui.label(format!(
+                    "{resource}: {:.2}/{:.2}",
+                    pool.current(),
+                    pool.max()
+                ));
+            }
+        }
+
+        if let Ok(resistances) = resistances.get(world, model_e) {
+            let resistances_str = resistances
+                .iter()
+                .map(|(damage_type, resistance)| {
+                    format!("{damage_type} {:.0}%", resistance * 100.0)
+                })
+                .join(", ");
+
+            ui.label(format!("Resistances: {resistances_str}"));
+        }
     });
 
     if holds_ability_slots.get(world, model_e).is_ok() {
@@ -369,6 +415,7 @@ fn ui_ability_slots(
         FightInterface,
         AbilityInterface,
         OngoingCastInterface,
+        MessageWriter<GameCommand>,
     )>,
 ) -> (Ui, AbilitySlotsSectionUiState) {
     // TODO: add colors (again) at some point (if it fits..)
@@ -384,6 +431,7 @@ fn ui_ability_slots(
             fight_interface,
             ability_interface,
             ongoing_cast_interface,
+            mut game_commands,
         ) = params.get_mut(world);
 
         let user_interactable = slots_section_state.user_interactable
@@ -454,16 +502,27 @@ fn ui_ability_slots(
                     let ability = ability_interface.get_ability_from_entity(ongoing_cast.ability_e);
 
                     ui.indent(Id::new("progress_bar_for_slot").with(slot_e), |ui| {
-                        let progress_text = format!(
-                            "{} - {}",
-                            ability.name.clone(),
-                            format_remaining_time(&remaining),
-                        );
-
-                        ProgressBar::new(progress)
-                            .animate(false) // animates a spinner, didn't like it super much.
-                            .text(progress_text)
-                            .ui(ui);
+                        ui.horizontal(|ui: &mut Ui| {
+                            if user_interactable && ui.small_button("x").clicked() {
+                                let cancel_cast = CancelCast {
+                                    caster_e: model_e,
+                                    slot_e,
+                                    fight_e,
+                                };
+                                game_commands.write(GameCommand::new_from_user(cancel_cast.into()));
+                            }
+
+                            let progress_text = format!(
+                                "{} - {}",
+                                ability.name.clone(),
+                                format_remaining_time(&remaining),
+                            );
+
+                            ProgressBar::new(progress)
+                                .animate(false) // animates a spinner, didn't like it super much.
+                                .text(progress_text)
+                                .ui(ui);
+                        });
                     });
                 }
             }
@@ -489,9 +548,12 @@ fn ui_abilities(
         Query<&Holds<Ability>>,
         Query<&Holds<AbilitySlot>>,
         Query<&Cooldown>,
+        Query<&AbilityCharges>,
         Query<&AbilitySlotRequirement>,
+        Query<&AbilityCost>,
         AbilityInterface,
         AbilityCastingInterface,
+        AbilityStatsInterface,
         MessageWriter<GameCommand>,
     )>,
 ) -> (Ui, FightColumnUiState) {
@@ -501,9 +563,12 @@ fn ui_abilities(
             holds_abilities,
             holds_ability_slots,
             cooldowns,
+            ability_charges,
             ability_slot_requirements,
+            ability_costs,
             ability_interface,
             ability_casting_interface,
+            ability_stats_interface,
             mut game_commands,
         ) = params.get_mut(world);
 
@@ -512,6 +577,13 @@ fn ui_abilities(
 
         ui.heading("Abilities");
 
+        let active_crowd_control = ability_casting_interface
+            .crowd_control_interface
+            .active_crowd_control(model_e);
+        if !active_crowd_control.is_empty() {
+            ui.label(RichText::new(active_crowd_control.iter().join(", ")).color(Color32::ORANGE));
+        }
+
         ui.indent(ui.id().with("abilities"), |ui: &mut Ui| {
             for (idx, ability_e) in holds_abilities.relationship_sources(model_e).enumerate() {
                 let ability = ability_interface.get_ability_from_entity(ability_e);
@@ -563,15 +635,45 @@ fn ui_abilities(
                         let shortcut_pressed =
                             monospace_checked_shortcut(ui, keyboard_shortcut.as_ref());
 
-                        if let Ok(active_cooldown) = cooldowns.get(ability_e)
-                            && !active_cooldown.remaining_cooldown().is_zero()
-                        {
-                            let cooldown_str =
-                                format_remaining_time(&active_cooldown.remaining_cooldown());
+                        // the ability is on cooldown for the longer of its own and its group's
+                        let remaining_cooldown = cooldowns
+                            .get(ability_e)
+                            .map(Cooldown::remaining_cooldown)
+                            .unwrap_or_default()
+                            .max(
+                                ability_casting_interface
+                                    .cooldown_interface
+                                    .remaining_group_cooldown(model_e, ability_e)
+                                    .unwrap_or_default(),
+                            );
+                        if !remaining_cooldown.is_zero() {
+                            let cooldown_str = format_remaining_time(&remaining_cooldown);
 
                             // explicit black, because this label will be disabled, but we want the
                             // cooldown str to not be greyed out.
                             ui.colored_label(Color32::BLACK, cooldown_str);
+                        } else if ability_casting_interface
+                            .cooldown_interface
+                            .is_on_cooldown(ability_e)
+                        {
+                            // the cooldown only starts once the ability's effect ended
+                            ui.colored_label(Color32::BLACK, "-");
+                        }
+
+                        if let Ok(charges) = ability_charges.get(ability_e) {
+                            let mut charges_str =
+                                format!("{}/{}", charges.current_charges(), charges.max_charges());
+                            if let Some(time_to_next_charge) = charges.time_to_next_charge() {
+                                write!(
+                                    charges_str,
+                                    " ({})",
+                                    format_remaining_time(&time_to_next_charge)
+                                )
+                                .unwrap();
+                            }
+
+                            // same as the cooldown, this should not be greyed out without charges
+                            ui.colored_label(Color32::BLACK, charges_str);
                         }
 
                         let ability_button = ui.add_enabled(
@@ -579,6 +681,25 @@ fn ui_abilities(
                             egui::Button::new(ability.name.clone()),
                         );
 
+                        // sweep over the buttons of all abilities on the global cooldown, while
+                        // it is running. the remaining part of the button is covered.
+                        let cooldown_interface = &ability_casting_interface.cooldown_interface;
+                        if let Some(global_cooldown) =
+                            cooldown_interface.active_global_cooldown(model_e)
+                            && cooldown_interface.is_on_global_cooldown(ability_e)
+                        {
+                            let mut sweep_rect = ability_button.rect;
+                            sweep_rect.set_width(
+                                sweep_rect.width() * global_cooldown.fraction_remaining(),
+                            );
+
+                            ui.painter().rect_filled(
+                                sweep_rect,
+                                ui.visuals().widgets.inactive.corner_radius,
+                                Color32::from_black_alpha(96),
+                            );
+                        }
+
                         // `hovered()`, `show_tooltip_at_pointer()`, etc., all don't work when
                         // disabled. but we wan't tooltips for disabled abilities as well.
                         if ability_button.contains_pointer() {
@@ -602,6 +723,8 @@ fn ui_abilities(
                             .show(tooltip_for_ability(
                                 ability.clone(),
                                 ability_slot_requirements.get(ability_e).ok(),
+                                ability_costs.get(ability_e).ok(),
+                                ability_stats_interface.resolved_stats(ability_e),
                             ));
                         }
 
@@ -627,24 +750,22 @@ fn ui_abilities(
 fn ui_effects(
     In((mut ui, (model_e,))): In<(Ui, (Entity,))>,
     world: &mut World,
-    params: &mut SystemState<(Query<&HasEffects>, Query<&Children>, Res<AppTypeRegistry>)>,
+    params: &mut SystemState<(Query<&HasEffects>, Res<AppTypeRegistry>)>,
 ) -> (Ui, ()) {
     ui.heading("Effects");
 
     let (effect_entities, app_type_registry) = {
-        let (has_effects, children, world_type_registry) = params.get_mut(world);
-        let holder = has_effects.get(model_e).unwrap().holder();
-        let children = children
-            .get(holder)
-            .ok()
-            .map(|cs| cs.to_vec())
+        let (has_effects, world_type_registry) = params.get_mut(world);
+        let effect_entities = has_effects
+            .get(model_e)
+            .map(|has_effects| has_effects.effects().to_vec())
             .unwrap_or_default();
 
         let app_type_registry = world_type_registry.clone();
 
         params.apply(world);
 
-        (children, app_type_registry)
+        (effect_entities, app_type_registry)
     };
 
     let type_registry = app_type_registry.read();
@@ -716,13 +837,46 @@ fn ui_effects(
                 .get(comp_as_reflect)
                 .unwrap();
 
-            comp_as_render_game_effect_immediate.render_to_ui(&mut ui);
+            ui.horizontal(|ui: &mut Ui| {
+                if let Some(classification) = world.get::<EffectClassification>(effect_e) {
+                    render_effect_classification(ui, classification);
+                }
+
+                comp_as_render_game_effect_immediate
+                    .render_to_ui(ui, world.get::<EffectTimer>(effect_e));
+
+                if let Some(stacks) = world.get::<EffectStacks>(effect_e)
+                    && stacks.count() > 1
+                {
+                    ui.label(format!("x{}", stacks.count()));
+                }
+            });
         }
     }
 
     (ui, ())
 }
 
+/// Marks buffs with a green `+` and debuffs with a red `-`, details are shown on hover.
+fn render_effect_classification(ui: &mut Ui, classification: &EffectClassification) {
+    let (marker, color) = match classification.disposition {
+        EffectDisposition::Buff => ("+", Color32::GREEN),
+        EffectDisposition::Debuff => ("-", Color32::RED),
+    };
+
+    ui.label(RichText::new(marker).strong().monospace().color(color))
+        .on_hover_text(format!(
+            "{category} {disposition}{dispellable}",
+            category = classification.category,
+            disposition = classification.disposition,
+            dispellable = if classification.dispellable {
+                ""
+            } else {
+                " (not dispellable)"
+            },
+        ));
+}
+
 fn monospace_checked_shortcut(ui: &mut Ui, shortcut: Option<&KeyboardShortcut>) -> bool {
     let shortcut_pressed: bool = match shortcut {
         // TODO: somehow check if the current egui window has keyboard focus.
@@ -744,6 +898,8 @@ fn monospace_checked_shortcut(ui: &mut Ui, shortcut: Option<&KeyboardShortcut>)
 fn tooltip_for_ability(
     ability: Ability,
     slot_requirement: Option<&AbilitySlotRequirement>,
+    cost: Option<&AbilityCost>,
+    stats: AbilityStats,
 ) -> impl FnOnce(&mut Ui) {
     move |ui| {
         if let Some(req) = slot_requirement {
@@ -753,7 +909,19 @@ fn tooltip_for_ability(
             ));
         }
 
-        ui.label(ability.description.clone());
+        if let Some(cost) = cost {
+            ui.label(format!("Cost: {} {}\n", cost.amount, cost.resource));
+        }
+
+        ui.label(stats.format_description(&ability.description));
+
+        if !stats.is_empty() {
+            ui.label("");
+
+            for (stat, value) in stats.iter() {
+                ui.label(format!("{}: {value}", stat.display_name()));
+            }
+        }
     }
 }
 
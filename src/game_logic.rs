@@ -3,15 +3,22 @@ use bevy::prelude::*;
 pub mod ability;
 pub mod ability_casting;
 pub mod ability_slots;
+pub mod ability_stats;
+pub mod absorb_shield;
 pub mod ai_behavior;
+pub mod auras;
+pub mod character_stats;
 pub mod commands;
 pub mod cooldown;
+pub mod crowd_control;
 pub mod damage_resolution;
 pub mod effects;
 pub mod faction;
 pub mod fight;
+pub mod healing;
 pub mod health;
 pub mod ongoing_cast;
+pub mod resource_pools;
 
 pub struct GameLogicPlugin;
 
@@ -22,15 +29,24 @@ impl Plugin for GameLogicPlugin {
             ability::AbilityPlugin,
             ability_casting::AbilityCastingPlugin,
             ability_slots::AbilitySlotsPlugin,
+            ability_stats::AbilityStatsPlugin,
+            absorb_shield::AbsorbShieldPlugin,
             ai_behavior::AiBehaviorPlugin,
+            auras::AurasPlugin,
+            character_stats::CharacterStatsPlugin,
             commands::CommandsPlugin,
             cooldown::CooldownPlugin,
+            crowd_control::CrowdControlPlugin,
+        ))
+        .add_plugins((
             damage_resolution::DamageResolutionPlugin,
             effects::EffectsPlugin,
             faction::FactionPlugin,
             fight::FightPlugin,
+            healing::HealingPlugin,
             health::HealthInterfacePlugin,
             ongoing_cast::OngoingCastPlugin,
+            resource_pools::ResourcePoolsPlugin,
         ));
     }
 }
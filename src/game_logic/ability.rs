@@ -1,18 +1,30 @@
 use std::borrow::Cow;
 
 use bevy::prelude::*;
+use serde::Deserialize;
 
 use crate::{
     game_logic::ability_slots::AbilitySlotType,
     utils::holds_held::{Held, Holds},
 };
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Reflect, Deserialize)]
 pub enum AbilityId {
     WeaponAttack,
     NeedlingHex,
     ChargedStrike,
     PreparedBlock,
+    Rejuvenation,
+    ShieldBash,
+    Kick,
+    Weaken,
+    Haste,
+    Cleanse,
+    Barrier,
+    WitheringAura,
+    VolatileCharge,
+    /// Abilities that are only defined through data, see [`crate::abilities::definitions`].
+    Custom(Cow<'static, str>),
 }
 
 #[derive(Debug, Clone, Component, Reflect)]
@@ -22,15 +34,44 @@ pub struct Ability {
     pub description: Cow<'static, str>,
 }
 
-#[derive(Debug, Clone, Component, Reflect)]
+#[derive(Debug, Clone, Component, Reflect, Deserialize)]
 pub struct AbilitySlotRequirement(pub AbilitySlotType);
 
-#[derive(Debug, Clone, Component, Reflect, Default)]
+#[derive(Debug, Clone, Component, Reflect, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct AbilityCooldown {
     pub duration: std::time::Duration,
 }
 
-#[derive(Debug, Clone, Component, Reflect)]
+/// The name of a cooldown category that is shared by multiple abilities, e.g. "potions".
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect, Deserialize)]
+#[serde(transparent)]
+pub struct CooldownGroup(pub Cow<'static, str>);
+
+/// Puts the ability into a [`CooldownGroup`]. Using it puts all abilities of the group on a shared
+/// cooldown for `duration`, which is tracked on the character, see [`GroupCooldowns`].
+///
+/// [`GroupCooldowns`]: crate::game_logic::cooldown::GroupCooldowns
+#[derive(Debug, Clone, Component, Reflect, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AbilityCooldownGroup {
+    pub group: CooldownGroup,
+    pub duration: std::time::Duration,
+}
+
+/// Marks an ability as being on the global cooldown: using it starts the [`GlobalCooldown`] of the
+/// caster, and it can't be used while that is running. Abilities without it can still be used
+/// during the global cooldown.
+///
+/// [`GlobalCooldown`]: crate::game_logic::cooldown::GlobalCooldown
+#[derive(Debug, Clone, Copy, Component, Reflect, Default)]
+pub struct OnGlobalCooldown;
+
+/// Resets the cooldown of an ability when its damage kills the target.
+#[derive(Debug, Clone, Copy, Component, Reflect, Default)]
+pub struct ResetCooldownOnKill;
+
+#[derive(Debug, Clone, Component, Reflect, Deserialize)]
 pub struct AbilityCastTime(pub std::time::Duration);
 
 #[derive(EntityEvent, Debug, Reflect)]
@@ -46,8 +87,14 @@ pub struct PerformAbility {
 pub enum CastFailureReason {
     AbilityCooldown,
     SlotCooldown,
+    GlobalCooldown,
+    GroupCooldown,
     SlotRequirement,
     FightEnded,
+    InsufficientResource,
+    Stunned,
+    Silenced,
+    Disarmed,
 }
 
 pub struct AbilityPlugin;
@@ -59,6 +106,9 @@ impl Plugin for AbilityPlugin {
             .register_type::<Ability>()
             .register_type::<AbilitySlotRequirement>()
             .register_type::<AbilityCooldown>()
+            .register_type::<AbilityCooldownGroup>()
+            .register_type::<OnGlobalCooldown>()
+            .register_type::<ResetCooldownOnKill>()
             .register_type::<AbilityCastTime>()
             .register_type::<PerformAbility>()
             .register_type::<CastFailureReason>();
@@ -1,22 +1,32 @@
 use std::time::Duration;
 
-use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy::{ecs::system::SystemParam, platform::collections::HashSet, prelude::*};
 use derive_more::{Display, Error};
 
 use super::{
     ability::{
-        AbilityCastTime, AbilityCooldown, AbilityId, AbilitySlotRequirement, CastFailureReason,
-        PerformAbility,
+        AbilityCastTime, AbilityId, AbilitySlotRequirement, CastFailureReason, PerformAbility,
     },
     ability_slots::AbilitySlot,
+    character_stats::{CharacterStat, CharacterStatsInterface},
     commands::{GameCommand, GameCommandKind},
+    crowd_control::CrowdControlInterface,
     fight::{FightInterface, FightStatus},
-    ongoing_cast::{OngoingCast, OngoingCastFinishedSuccessfully, OngoingCastInterface},
+    ongoing_cast::{
+        CastAbortReason, OngoingCast, OngoingCastAborted, OngoingCastFinishedSuccessfully,
+        OngoingCastInterface,
+    },
+    resource_pools::{AbilityCost, CostPayment, ResourcePoolsInterface},
 };
 use crate::{
     PerUpdateSet,
     abilities::AbilityInterface,
-    game_logic::{ability::Ability, cooldown::Cooldown},
+    game_logic::{
+        ability::Ability,
+        cooldown::{AbilityCharges, Cooldown, CooldownInterface, CooldownStart},
+        effects::{EffectApplied, EffectExpired, EffectRemoved},
+    },
+    utils::holds_held::{Held, Holds},
 };
 
 #[derive(SystemParam)]
@@ -24,10 +34,17 @@ pub struct AbilityCastingInterface<'w, 's> {
     abilities: Query<'w, 's, &'static Ability>,
     ability_slots: Query<'w, 's, &'static AbilitySlot>,
     ability_slot_requirements: Query<'w, 's, &'static AbilitySlotRequirement>,
-    has_cooldown: Query<'w, 's, Has<Cooldown>>,
+    cooldowns: Query<'w, 's, &'static Cooldown>,
+    ability_charges: Query<'w, 's, &'static AbilityCharges>,
+    ability_costs: Query<'w, 's, &'static AbilityCost>,
+    holds_slots: Query<'w, 's, &'static Holds<AbilitySlot>>,
     pub ability_interface: AbilityInterface<'w, 's>,
+    pub cooldown_interface: CooldownInterface<'w, 's>,
     pub fight_interface: FightInterface<'w, 's>,
     pub ongoing_cast_interface: OngoingCastInterface<'w, 's>,
+    pub resource_pools_interface: ResourcePoolsInterface<'w, 's>,
+    pub crowd_control_interface: CrowdControlInterface<'w, 's>,
+    commands: Commands<'w, 's>,
 }
 
 /// Represents the usage of an ability
@@ -40,11 +57,25 @@ pub struct UseAbility {
     pub fight_e: Entity,
 }
 
+/// Cancels the ongoing cast on a slot of the caster
+#[derive(Debug, Clone, Component, Reflect)]
+pub struct CancelCast {
+    pub caster_e: Entity,
+    pub slot_e: Entity,
+    pub fight_e: Entity,
+}
+
 #[derive(Debug, Display, Error)]
 pub enum InvalidCastReason {
     FightEnded,
     AbilityOrSlotOnCooldown,
+    GlobalCooldown,
+    GroupCooldown,
     CantUseSlot,
+    InsufficientResource,
+    Stunned,
+    Silenced,
+    Disarmed,
 }
 
 impl<'w, 's> AbilityCastingInterface<'w, 's> {
@@ -66,20 +97,57 @@ impl<'w, 's> AbilityCastingInterface<'w, 's> {
         };
 
         // Check cooldowns
-        if self
-            .has_cooldown
-            .iter_many([cast.ability_e, cast.slot_e])
-            .any(|has_cd| has_cd)
+        if [cast.ability_e, cast.slot_e]
+            .into_iter()
+            .any(|entity| self.cooldown_interface.is_on_cooldown(entity))
+            || self
+                .ability_charges
+                .get(cast.ability_e)
+                .is_ok_and(|charges| !charges.has_charge())
         {
             return Err(InvalidCastReason::AbilityOrSlotOnCooldown);
         }
 
+        if self
+            .cooldown_interface
+            .is_blocked_by_global_cooldown(cast.caster_e, cast.ability_e)
+        {
+            return Err(InvalidCastReason::GlobalCooldown);
+        }
+
+        if self
+            .cooldown_interface
+            .is_blocked_by_group_cooldown(cast.caster_e, cast.ability_e)
+        {
+            return Err(InvalidCastReason::GroupCooldown);
+        }
+
+        let Ok(slot) = self.ability_slots.get(cast.slot_e) else {
+            return Err(InvalidCastReason::CantUseSlot);
+        };
+
         // Check slot requirement
-        if let Ok(requirement) = self.ability_slot_requirements.get(cast.ability_e) {
-            let slot = self.ability_slots.get(cast.slot_e).unwrap();
-            if requirement.0 != slot.tpe {
-                return Err(InvalidCastReason::CantUseSlot);
-            }
+        if let Ok(requirement) = self.ability_slot_requirements.get(cast.ability_e)
+            && requirement.0 != slot.tpe
+        {
+            return Err(InvalidCastReason::CantUseSlot);
+        }
+
+        // Check crowd control
+        if let Some(cc) = self
+            .crowd_control_interface
+            .blocking_crowd_control(cast.caster_e, slot.tpe)
+        {
+            return Err(cc.invalid_cast_reason());
+        }
+
+        // Check resource cost
+        if let Ok(cost) = self.ability_costs.get(cast.ability_e)
+            && !self
+                .resource_pools_interface
+                .can_afford(cast.caster_e, cost)
+        {
+            return Err(InvalidCastReason::InsufficientResource);
         }
 
         Ok(())
@@ -102,30 +170,112 @@ impl<'w, 's> AbilityCastingInterface<'w, 's> {
         self.ongoing_cast_interface.start_new_cast(slot_e, cast)
     }
 
+    /// Pays the cost of `ability_e` if it has to be paid when the cast starts. Returns `false` if
+    /// the caster can't afford it.
+    pub fn pay_cost_on_cast_start(&mut self, caster_e: Entity, ability_e: Entity) -> bool {
+        match self.ability_costs.get(ability_e) {
+            Ok(cost) if cost.payment == CostPayment::OnCastStart => {
+                self.resource_pools_interface.pay(caster_e, cost).is_ok()
+            }
+            _ => true,
+        }
+    }
+
+    /// Interrupts all ongoing casts of `target_e`. Optionally locks out the interrupted slots,
+    /// i.e., puts them on cooldown for (at least) `lockout`. Returns `true` if a cast was
+    /// interrupted.
+    pub fn interrupt_casts_of(
+        &mut self,
+        target_e: Entity,
+        interrupter_e: Option<Entity>,
+        lockout: Option<Duration>,
+    ) -> bool {
+        let casting_slots = self
+            .holds_slots
+            .relationship_sources(target_e)
+            .filter(|slot_e| {
+                self.ongoing_cast_interface
+                    .get_ongoing_cast(*slot_e)
+                    .is_some()
+            })
+            .collect::<Vec<_>>();
+
+        for &slot_e in &casting_slots {
+            self.ongoing_cast_interface
+                .cancel_ongoing_cast(slot_e, CastAbortReason::Interrupted { by: interrupter_e });
+
+            if let Some(lockout) = lockout {
+                self.lock_out_slot(slot_e, lockout);
+            }
+        }
+
+        !casting_slots.is_empty()
+    }
+
+    /// Puts the slot on cooldown for `lockout`, unless it already has a longer cooldown
+    fn lock_out_slot(&mut self, slot_e: Entity, lockout: Duration) {
+        let remaining = self
+            .cooldowns
+            .get(slot_e)
+            .map(Cooldown::remaining_cooldown)
+            .unwrap_or_default();
+
+        if remaining < lockout {
+            self.commands.entity(slot_e).insert(Cooldown::new(lockout));
+        }
+    }
+
     /// Interrupts any ongoing cast on the specified slot (low-level method)
     fn interrupt_cast_on_slot(&mut self, slot_e: Entity) {
-        self.ongoing_cast_interface.cancel_ongoing_cast(slot_e);
+        self.ongoing_cast_interface
+            .cancel_ongoing_cast(slot_e, CastAbortReason::Replaced);
     }
 }
 
 /// Spawns a CastRequest entity for each UseAbility command
 fn request_ability_cast(mut commands: Commands, mut game_commands: MessageReader<GameCommand>) {
     for command in game_commands.read() {
-        #[expect(irrefutable_let_patterns, reason = "We only have UseAbility currently")]
         if let GameCommandKind::UseAbility(use_ability) = &command.kind {
             commands.spawn(use_ability.clone());
         }
     }
 }
 
-/// Checks if the ability is on cooldown
+/// Cancels the ongoing cast for each CancelCast command
+fn process_cast_cancellations(
+    mut game_commands: MessageReader<GameCommand>,
+    held_slots: Query<&Held<AbilitySlot>>,
+    mut ongoing_cast_interface: OngoingCastInterface,
+) {
+    for command in game_commands.read() {
+        let GameCommandKind::CancelCast(cancel_cast) = &command.kind else {
+            continue;
+        };
+
+        if held_slots.related::<Held<AbilitySlot>>(cancel_cast.slot_e) != Some(cancel_cast.caster_e)
+        {
+            warn!("Can't cancel cast on a slot of another character: {cancel_cast:?}");
+            continue;
+        }
+
+        ongoing_cast_interface
+            .cancel_ongoing_cast(cancel_cast.slot_e, CastAbortReason::CancelledByOwner);
+    }
+}
+
+/// Checks if the ability is on cooldown, or has no charges left
 fn check_ability_cooldowns(
     cast_requests: Query<(Entity, &UseAbility), Without<CastFailureReason>>,
-    has_cooldown: Query<Has<Cooldown>>,
+    cooldown_interface: CooldownInterface,
+    ability_charges: Query<&AbilityCharges>,
     mut commands: Commands,
 ) {
     for (req_e, use_ability) in cast_requests.iter() {
-        if has_cooldown.get(use_ability.ability_e).unwrap_or(false) {
+        if cooldown_interface.is_on_cooldown(use_ability.ability_e)
+            || ability_charges
+                .get(use_ability.ability_e)
+                .is_ok_and(|charges| !charges.has_charge())
+        {
             commands
                 .entity(req_e)
                 .insert(CastFailureReason::AbilityCooldown);
@@ -135,11 +285,11 @@ fn check_ability_cooldowns(
 
 fn check_slot_cooldowns(
     cast_requests: Query<(Entity, &UseAbility), Without<CastFailureReason>>,
-    has_cooldown: Query<Has<Cooldown>>,
+    cooldown_interface: CooldownInterface,
     mut commands: Commands,
 ) {
     for (req_e, use_ability) in cast_requests.iter() {
-        if has_cooldown.get(use_ability.slot_e).unwrap_or(false) {
+        if cooldown_interface.is_on_cooldown(use_ability.slot_e) {
             commands
                 .entity(req_e)
                 .insert(CastFailureReason::SlotCooldown);
@@ -147,6 +297,38 @@ fn check_slot_cooldowns(
     }
 }
 
+fn check_global_cooldowns(
+    cast_requests: Query<(Entity, &UseAbility), Without<CastFailureReason>>,
+    cooldown_interface: CooldownInterface,
+    mut commands: Commands,
+) {
+    for (req_e, use_ability) in cast_requests.iter() {
+        if cooldown_interface
+            .is_blocked_by_global_cooldown(use_ability.caster_e, use_ability.ability_e)
+        {
+            commands
+                .entity(req_e)
+                .insert(CastFailureReason::GlobalCooldown);
+        }
+    }
+}
+
+fn check_group_cooldowns(
+    cast_requests: Query<(Entity, &UseAbility), Without<CastFailureReason>>,
+    cooldown_interface: CooldownInterface,
+    mut commands: Commands,
+) {
+    for (req_e, use_ability) in cast_requests.iter() {
+        if cooldown_interface
+            .is_blocked_by_group_cooldown(use_ability.caster_e, use_ability.ability_e)
+        {
+            commands
+                .entity(req_e)
+                .insert(CastFailureReason::GroupCooldown);
+        }
+    }
+}
+
 fn check_slot_requirements(
     cast_requests: Query<(Entity, &UseAbility), Without<CastFailureReason>>,
     abilities: Query<&AbilitySlotRequirement>,
@@ -182,20 +364,93 @@ fn check_slot_requirements(
     }
 }
 
+fn check_crowd_control(
+    cast_requests: Query<(Entity, &UseAbility), Without<CastFailureReason>>,
+    slots: Query<&AbilitySlot>,
+    crowd_control_interface: CrowdControlInterface,
+    mut commands: Commands,
+) {
+    for (req_e, use_ability) in cast_requests.iter() {
+        let Ok(slot) = slots.get(use_ability.slot_e) else {
+            continue;
+        };
+
+        if let Some(cc) =
+            crowd_control_interface.blocking_crowd_control(use_ability.caster_e, slot.tpe)
+        {
+            commands.entity(req_e).insert(cc.cast_failure_reason());
+        }
+    }
+}
+
+fn check_resource_costs(
+    cast_requests: Query<(Entity, &UseAbility), Without<CastFailureReason>>,
+    ability_costs: Query<&AbilityCost>,
+    resource_pools_interface: ResourcePoolsInterface,
+    mut commands: Commands,
+) {
+    for (req_e, use_ability) in cast_requests.iter() {
+        if let Ok(cost) = ability_costs.get(use_ability.ability_e)
+            && !resource_pools_interface.can_afford(use_ability.caster_e, cost)
+        {
+            commands
+                .entity(req_e)
+                .insert(CastFailureReason::InsufficientResource);
+        }
+    }
+}
+
 fn process_valid_casts(
     cast_requests: Query<(Entity, &UseAbility), Without<CastFailureReason>>,
     mut ability_casting_interface: AbilityCastingInterface,
     ability_cast_times: Query<&AbilityCastTime>,
+    character_stats: CharacterStatsInterface,
     mut commands: Commands,
 ) {
+    let mut global_cooldowns_started = HashSet::new();
+
     for (req_e, use_ability) in cast_requests.iter() {
+        let starts_global_cooldown = ability_casting_interface
+            .cooldown_interface
+            .starts_global_cooldown(use_ability.caster_e, use_ability.ability_e);
+
+        // Another request in the same frame might have started the global cooldown already.
+        if starts_global_cooldown && global_cooldowns_started.contains(&use_ability.caster_e) {
+            commands
+                .entity(req_e)
+                .insert(CastFailureReason::GlobalCooldown);
+            continue;
+        }
+
+        // Another request in the same frame might have spent the resource already.
+        if !ability_casting_interface
+            .pay_cost_on_cast_start(use_ability.caster_e, use_ability.ability_e)
+        {
+            commands
+                .entity(req_e)
+                .insert(CastFailureReason::InsufficientResource);
+            continue;
+        }
+
+        if starts_global_cooldown {
+            ability_casting_interface
+                .cooldown_interface
+                .start_global_cooldown(use_ability.caster_e);
+            global_cooldowns_started.insert(use_ability.caster_e);
+        }
+
         // Use the slot (interrupts, applies slot on-use cooldown)
         ability_casting_interface.use_slot(use_ability.slot_e);
 
-        let cast_duration = ability_cast_times
+        let base_cast_duration = ability_cast_times
             .get(use_ability.ability_e)
             .map(|ct| ct.0)
             .unwrap_or(Duration::ZERO);
+        let cast_duration = character_stats.modified_duration(
+            use_ability.caster_e,
+            CharacterStat::CastTime,
+            base_cast_duration,
+        );
 
         let ongoing_cast = OngoingCast {
             ability_e: use_ability.ability_e,
@@ -222,36 +477,130 @@ fn cleanup_failed_casts(
     }
 }
 
+/// Observer that starts the ability and slot cooldowns that start with the cast (see
+/// [`CooldownTiming`]) when an ongoing cast starts
+///
+/// [`CooldownTiming`]: crate::game_logic::cooldown::CooldownTiming
+fn start_cooldowns_on_cast_start(
+    trigger: On<Insert, OngoingCast>,
+    ongoing_casts: Query<&OngoingCast>,
+    mut cooldown_interface: CooldownInterface,
+) {
+    let slot_e = trigger.entity;
+    let Ok(ongoing_cast) = ongoing_casts.get(slot_e) else {
+        return;
+    };
+
+    for entity in [ongoing_cast.ability_e, slot_e] {
+        cooldown_interface.start_cooldown_at(
+            entity,
+            ongoing_cast.ability_e,
+            CooldownStart::CastStart,
+        );
+    }
+}
+
 /// Observer that applies slot cooldowns when ongoing casts finish successfully
 fn apply_slot_cooldown_on_cast_finish(
     trigger: On<OngoingCastFinishedSuccessfully>,
-    ability_slots: Query<&AbilitySlot>,
-    mut commands: Commands,
+    mut cooldown_interface: CooldownInterface,
 ) {
-    let slot_e = trigger.event().slot_entity;
+    let event = trigger.event();
 
-    // Apply slot-defined cooldown if present
-    if let Ok(slot) = ability_slots.get(slot_e)
-        && let Some(cooldown_duration) = slot.on_use_cooldown
-    {
-        commands
-            .entity(slot_e)
-            .insert(Cooldown::new(cooldown_duration));
-    }
+    cooldown_interface.start_cooldown_at(
+        event.slot_entity,
+        event.ability_entity,
+        CooldownStart::CastFinish,
+    );
 }
 
-/// Observer that applies ability cooldowns when ongoing casts finish successfully
+/// Observer that applies ability and group cooldowns, and spends ability charges when ongoing
+/// casts finish successfully
 fn apply_ability_cooldown_on_cast_finish(
     trigger: On<OngoingCastFinishedSuccessfully>,
-    ability_cooldowns: Query<&AbilityCooldown>,
-    mut commands: Commands,
+    mut ability_charges: Query<&mut AbilityCharges>,
+    mut cooldown_interface: CooldownInterface,
 ) {
     let ability_e = trigger.event().ability_entity;
 
-    if let Ok(cooldown) = ability_cooldowns.get(ability_e) {
-        commands
-            .entity(ability_e)
-            .insert(Cooldown::new(cooldown.duration));
+    if let Some(caster_e) = trigger
+        .event()
+        .caster_entity
+        .or_else(|| cooldown_interface.find_character_of_cooldown(ability_e))
+    {
+        cooldown_interface.start_group_cooldown(caster_e, ability_e);
+    }
+
+    if let Ok(mut charges) = ability_charges.get_mut(ability_e)
+        && !charges.spend()
+    {
+        warn!("Cast finished, but ability has no charges left (anymore): {ability_e}");
+    }
+
+    cooldown_interface.start_cooldown_at(ability_e, ability_e, CooldownStart::CastFinish);
+}
+
+/// Observer that starts the [`CooldownTiming::on_interrupt`] cooldowns of the ability and slot of
+/// interrupted casts
+///
+/// [`CooldownTiming::on_interrupt`]: crate::game_logic::cooldown::CooldownTiming::on_interrupt
+fn apply_cooldowns_on_cast_interrupted(
+    trigger: On<OngoingCastAborted>,
+    mut cooldown_interface: CooldownInterface,
+) {
+    let event = trigger.event();
+
+    if let CastAbortReason::Interrupted { .. } = event.reason {
+        cooldown_interface.start_cooldown_on_interrupt(event.ability_entity);
+        cooldown_interface.start_cooldown_on_interrupt(event.target);
+    }
+}
+
+/// Observer that starts the cooldowns waiting for an effect of their ability to expire
+fn start_cooldowns_on_effect_expired(
+    trigger: On<EffectExpired>,
+    mut cooldown_interface: CooldownInterface,
+) {
+    if let Some(ability_e) = trigger.event().source.ability {
+        cooldown_interface.start_cooldowns_awaiting_effect_expiry(ability_e);
+    }
+}
+
+/// Observer that defers the cooldowns of abilities with [`CooldownStart::EffectExpiry`] until the
+/// effect they applied ended
+fn defer_cooldowns_on_effect_applied(
+    trigger: On<EffectApplied>,
+    mut cooldown_interface: CooldownInterface,
+) {
+    if let Some(ability_e) = trigger.event().source.ability {
+        cooldown_interface.defer_cooldowns_until_effect_expiry(ability_e);
+    }
+}
+
+/// Observer that starts the cooldowns waiting for an effect of their ability to be removed, e.g.,
+/// consumed or dispelled
+fn start_cooldowns_on_effect_removed(
+    trigger: On<EffectRemoved>,
+    mut cooldown_interface: CooldownInterface,
+) {
+    if let Some(ability_e) = trigger.event().source.ability {
+        cooldown_interface.start_cooldowns_awaiting_effect_expiry(ability_e);
+    }
+}
+
+/// Observer that refunds ability costs that were paid on cast start when a cast is aborted
+fn refund_ability_cost_on_cast_aborted(
+    trigger: On<OngoingCastAborted>,
+    ability_costs: Query<&AbilityCost>,
+    mut resource_pools_interface: ResourcePoolsInterface,
+) {
+    let event = trigger.event();
+
+    if let Ok(cost) = ability_costs.get(event.ability_entity)
+        && cost.payment == CostPayment::OnCastStart
+        && let Some(caster_e) = event.caster_entity
+    {
+        resource_pools_interface.refund(caster_e, cost);
     }
 }
 
@@ -271,10 +620,19 @@ pub struct AbilityCastingPlugin;
 
 impl Plugin for AbilityCastingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(apply_slot_cooldown_on_cast_finish)
+        app.add_observer(start_cooldowns_on_cast_start)
+            .add_observer(apply_slot_cooldown_on_cast_finish)
             .add_observer(apply_ability_cooldown_on_cast_finish)
+            .add_observer(apply_cooldowns_on_cast_interrupted)
+            .add_observer(defer_cooldowns_on_effect_applied)
+            .add_observer(start_cooldowns_on_effect_expired)
+            .add_observer(start_cooldowns_on_effect_removed)
+            .add_observer(refund_ability_cost_on_cast_aborted)
+            // after the cooldown observers, so cooldowns started on cast finish exist once the
+            // performed ability applies its effects
             .add_observer(trigger_perform_ability)
             .register_type::<UseAbility>()
+            .register_type::<CancelCast>()
             .add_systems(
                 FixedUpdate,
                 (
@@ -282,12 +640,24 @@ impl Plugin for AbilityCastingPlugin {
                     (
                         check_ability_cooldowns,
                         check_slot_cooldowns,
+                        check_global_cooldowns,
+                        check_group_cooldowns,
                         check_slot_requirements,
+                        check_crowd_control,
+                        check_resource_costs,
                     ),
-                    (process_valid_casts, cleanup_failed_casts),
+                    process_valid_casts,
+                    cleanup_failed_casts,
                 )
                     .chain()
                     .in_set(PerUpdateSet::CommandResolution),
+            )
+            .add_systems(
+                FixedUpdate,
+                // before new requests, so a cast can be cancelled and replaced in the same update
+                process_cast_cancellations
+                    .before(request_ability_cast)
+                    .in_set(PerUpdateSet::CommandResolution),
             );
     }
 }
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
+use serde::Deserialize;
 
 use crate::utils::holds_held::{Held, Holds};
 
@@ -10,7 +11,7 @@ pub struct AbilitySlot {
     pub on_use_cooldown: Option<Duration>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Deserialize)]
 pub enum AbilitySlotType {
     WeaponAttack,
     ShieldDefend,
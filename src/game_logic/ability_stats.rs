@@ -0,0 +1,435 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::Deserialize;
+
+use crate::{
+    game_logic::{
+        ability::{Ability, AbilityId},
+        effects::HasEffects,
+    },
+    utils::holds_held::Held,
+};
+
+/// Named properties of an ability, e.g., its damage or how often its effect ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect, Deserialize)]
+pub enum AbilityStat {
+    Damage,
+    Healing,
+    TickInterval,
+    NumTicks,
+    AbsorbAmount,
+    EffectDuration,
+    DispelCount,
+    /// Multiplies the damage dealt by the holder of the ability's effect.
+    OutgoingDamageMultiplier,
+    /// Multiplies the cast times of the holder of the ability's effect.
+    CastTimeMultiplier,
+    /// Multiplies the cooldown recovery rate of the holder of the ability's effect.
+    CooldownRateMultiplier,
+}
+
+impl AbilityStat {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AbilityStat::Damage => "Damage",
+            AbilityStat::Healing => "Healing",
+            AbilityStat::TickInterval => "Tick Interval",
+            AbilityStat::NumTicks => "Ticks",
+            AbilityStat::AbsorbAmount => "Absorb Amount",
+            AbilityStat::EffectDuration => "Duration",
+            AbilityStat::DispelCount => "Dispels",
+            AbilityStat::OutgoingDamageMultiplier => "Damage Dealt",
+            AbilityStat::CastTimeMultiplier => "Cast Time",
+            AbilityStat::CooldownRateMultiplier => "Cooldown Rate",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Deserialize)]
+pub enum StatValue {
+    Amount(f64),
+    Duration(Duration),
+    Count(u32),
+    /// A factor, displayed as percentage, e.g., `0.7` as `70%`.
+    Multiplier(f64),
+}
+
+impl StatValue {
+    fn apply_modifier(self, add: f64, multiply: f64) -> StatValue {
+        match self {
+            StatValue::Amount(amount) => StatValue::Amount(((amount + add) * multiply).max(0.0)),
+            StatValue::Duration(duration) => StatValue::Duration(Duration::from_secs_f64(
+                ((duration.as_secs_f64() + add) * multiply).max(0.0),
+            )),
+            StatValue::Count(count) => {
+                StatValue::Count(((count as f64 + add) * multiply).round().max(0.0) as u32)
+            }
+            StatValue::Multiplier(multiplier) => {
+                StatValue::Multiplier(((multiplier + add) * multiply).max(0.0))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for StatValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatValue::Amount(amount) => write!(f, "{amount}"),
+            StatValue::Duration(duration) => write!(f, "{}s", duration.as_secs_f64()),
+            StatValue::Count(count) => write!(f, "{count}"),
+            StatValue::Multiplier(multiplier) => {
+                write!(f, "{}%", (multiplier * 1000.0).round() / 10.0)
+            }
+        }
+    }
+}
+
+/// The (base) stats of an ability, should be on the same entity as an [`Ability`].
+///
+/// Use [`AbilityStatsInterface::resolved_stats()`] to get the stats with all modifiers applied.
+#[derive(Debug, Clone, Default, Component, Reflect, Deserialize)]
+#[serde(transparent)]
+pub struct AbilityStats(BTreeMap<AbilityStat, StatValue>);
+
+impl AbilityStats {
+    pub fn new(stats: impl IntoIterator<Item = (AbilityStat, StatValue)>) -> Self {
+        Self(stats.into_iter().collect())
+    }
+
+    pub fn get(&self, stat: AbilityStat) -> Option<StatValue> {
+        self.0.get(&stat).copied()
+    }
+
+    pub fn set(&mut self, stat: AbilityStat, value: StatValue) {
+        self.0.insert(stat, value);
+    }
+
+    pub fn amount(&self, stat: AbilityStat) -> Option<f64> {
+        match self.get(stat)? {
+            StatValue::Amount(amount) => Some(amount),
+            _ => None,
+        }
+    }
+
+    pub fn duration(&self, stat: AbilityStat) -> Option<Duration> {
+        match self.get(stat)? {
+            StatValue::Duration(duration) => Some(duration),
+            _ => None,
+        }
+    }
+
+    pub fn count(&self, stat: AbilityStat) -> Option<u32> {
+        match self.get(stat)? {
+            StatValue::Count(count) => Some(count),
+            _ => None,
+        }
+    }
+
+    pub fn multiplier(&self, stat: AbilityStat) -> Option<f64> {
+        match self.get(stat)? {
+            StatValue::Multiplier(multiplier) => Some(multiplier),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (AbilityStat, StatValue)> + '_ {
+        self.0.iter().map(|(stat, value)| (*stat, *value))
+    }
+
+    /// Replaces `{Stat}` placeholders in `template` with the respective values, e.g.,
+    /// `"Deals {Damage} damage."`.
+    pub fn format_description(&self, template: &str) -> String {
+        self.iter()
+            .fold(template.to_string(), |description, (stat, value)| {
+                description.replace(&format!("{{{stat:?}}}"), &value.to_string())
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Reflect)]
+pub enum StatModifierKind {
+    Add(f64),
+    Multiply(f64),
+}
+
+#[derive(Debug, Clone, Reflect)]
+pub struct AbilityStatModifier {
+    /// `None` applies to all abilities of the character.
+    pub ability: Option<AbilityId>,
+    pub stat: AbilityStat,
+    pub kind: StatModifierKind,
+}
+
+/// Modifiers for the stats of all abilities held by a character. Can be on the character itself,
+/// or on one of its effect entities, in which case they are removed together with the effect, see
+/// [`StatModifyingEffect::ability_stat_modifiers()`].
+///
+/// [`StatModifyingEffect::ability_stat_modifiers()`]: crate::game_logic::character_stats::StatModifyingEffect::ability_stat_modifiers
+#[derive(Debug, Clone, Default, Component, Reflect)]
+pub struct AbilityStatModifiers(pub Vec<AbilityStatModifier>);
+
+#[derive(SystemParam)]
+pub struct AbilityStatsInterface<'w, 's> {
+    abilities: Query<'w, 's, (&'static Ability, Option<&'static AbilityStats>)>,
+    held_abilities: Query<'w, 's, &'static Held<Ability>>,
+    has_effects: Query<'w, 's, &'static HasEffects>,
+    modifiers: Query<'w, 's, &'static AbilityStatModifiers>,
+}
+
+impl<'w, 's> AbilityStatsInterface<'w, 's> {
+    /// Returns the stats of `ability_e`, with the modifiers of its holder and the holder's effects
+    /// applied: first all additive modifiers are summed up, then the result is multiplied with all
+    /// multiplicative modifiers.
+    pub fn resolved_stats(&self, ability_e: Entity) -> AbilityStats {
+        let Ok((ability, stats)) = self.abilities.get(ability_e) else {
+            return AbilityStats::default();
+        };
+
+        let mut stats = stats.cloned().unwrap_or_default();
+
+        let Some(holder_e) = self.held_abilities.related::<Held<Ability>>(ability_e) else {
+            return stats;
+        };
+
+        let modifiers = self
+            .modifiers_of(holder_e)
+            .filter(|m| m.ability.as_ref().is_none_or(|id| *id == ability.id))
+            .collect::<Vec<_>>();
+
+        for (stat, value) in stats.0.iter_mut() {
+            let (add, multiply) = modifiers.iter().filter(|m| m.stat == *stat).fold(
+                (0.0, 1.0),
+                |(add, multiply), m| match m.kind {
+                    StatModifierKind::Add(a) => (add + a, multiply),
+                    StatModifierKind::Multiply(mul) => (add, multiply * mul),
+                },
+            );
+
+            *value = value.apply_modifier(add, multiply);
+        }
+
+        stats
+    }
+
+    fn modifiers_of(&self, character: Entity) -> impl Iterator<Item = &AbilityStatModifier> {
+        let effect_entities = self
+            .has_effects
+            .get(character)
+            .map(HasEffects::effects)
+            .unwrap_or_default();
+
+        self.modifiers
+            .get(character)
+            .into_iter()
+            .chain(self.modifiers.iter_many(effect_entities))
+            .flat_map(|modifiers| &modifiers.0)
+    }
+}
+
+#[derive(Debug)]
+pub struct AbilityStatsPlugin;
+
+impl Plugin for AbilityStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AbilityStats>()
+            .register_type::<AbilityStatModifiers>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+    use super::{
+        AbilityStat, AbilityStatModifier, AbilityStatModifiers, AbilityStats,
+        AbilityStatsInterface, StatModifierKind, StatValue,
+    };
+    use crate::{
+        game_logic::{
+            ability::{Ability, AbilityId},
+            character_stats::{CharacterStatModifier, StatModifyingEffect, insert_stat_modifiers},
+            commands::CommandsPlugin,
+            effects::{
+                EffectCategory, EffectClassification, EffectInterface, EffectSource, EffectsPlugin,
+                GameEffect, StackableEffect, StackingPolicy,
+            },
+            fight::FightPlugin,
+        },
+        utils::holds_held::Held,
+    };
+
+    #[derive(Debug, Component, Reflect)]
+    struct FasterTicksTestEffect;
+
+    impl GameEffect for FasterTicksTestEffect {}
+
+    impl StackableEffect for FasterTicksTestEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::Replace;
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::buff(EffectCategory::Magic);
+    }
+
+    impl StatModifyingEffect for FasterTicksTestEffect {
+        fn stat_modifiers(&self) -> Vec<CharacterStatModifier> {
+            Vec::new()
+        }
+
+        fn ability_stat_modifiers(&self) -> Vec<AbilityStatModifier> {
+            vec![AbilityStatModifier {
+                ability: None,
+                stat: AbilityStat::TickInterval,
+                kind: StatModifierKind::Multiply(0.5),
+            }]
+        }
+    }
+
+    #[test]
+    fn test_modifiers_are_applied_per_character() {
+        let mut world = World::new();
+
+        let stats = AbilityStats::new([
+            (AbilityStat::Damage, StatValue::Amount(10.0)),
+            (AbilityStat::NumTicks, StatValue::Count(5)),
+            (
+                AbilityStat::TickInterval,
+                StatValue::Duration(Duration::from_secs(1)),
+            ),
+        ]);
+        let ability = Ability {
+            id: AbilityId::NeedlingHex,
+            name: "Needling Hex".into(),
+            description: "Needling Hex".into(),
+        };
+
+        let modified_ability_e = world.spawn((ability.clone(), stats.clone())).id();
+        let plain_ability_e = world.spawn((ability, stats)).id();
+
+        world
+            .spawn(AbilityStatModifiers(vec![
+                AbilityStatModifier {
+                    ability: None,
+                    stat: AbilityStat::Damage,
+                    kind: StatModifierKind::Add(5.0),
+                },
+                AbilityStatModifier {
+                    ability: Some(AbilityId::NeedlingHex),
+                    stat: AbilityStat::Damage,
+                    kind: StatModifierKind::Multiply(2.0),
+                },
+                AbilityStatModifier {
+                    ability: Some(AbilityId::WeaponAttack),
+                    stat: AbilityStat::NumTicks,
+                    kind: StatModifierKind::Add(3.0),
+                },
+            ]))
+            .add_one_related::<Held<Ability>>(modified_ability_e);
+        world
+            .spawn_empty()
+            .add_one_related::<Held<Ability>>(plain_ability_e);
+
+        let (modified, plain) = world
+            .run_system_once(move |interface: AbilityStatsInterface| {
+                (
+                    interface.resolved_stats(modified_ability_e),
+                    interface.resolved_stats(plain_ability_e),
+                )
+            })
+            .unwrap();
+
+        assert_eq!(modified.amount(AbilityStat::Damage), Some(30.0));
+        assert_eq!(modified.count(AbilityStat::NumTicks), Some(5));
+        assert_eq!(plain.amount(AbilityStat::Damage), Some(10.0));
+    }
+
+    #[test]
+    fn test_modifiers_of_effects_are_applied_while_they_last() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .add_observer(insert_stat_modifiers::<FasterTicksTestEffect>);
+
+        let ability_e = app
+            .world_mut()
+            .spawn((
+                Ability {
+                    id: AbilityId::Rejuvenation,
+                    name: "Rejuvenation".into(),
+                    description: "Rejuvenation".into(),
+                },
+                AbilityStats::new([(
+                    AbilityStat::TickInterval,
+                    StatValue::Duration(Duration::from_secs(1)),
+                )]),
+            ))
+            .id();
+        let character_e = app
+            .world_mut()
+            .spawn_empty()
+            .add_one_related::<Held<Ability>>(ability_e)
+            .id();
+
+        let tick_interval = |app: &mut App| {
+            app.world_mut()
+                .run_system_once(move |interface: AbilityStatsInterface| {
+                    interface
+                        .resolved_stats(ability_e)
+                        .duration(AbilityStat::TickInterval)
+                })
+                .unwrap()
+        };
+
+        let effect_e = app
+            .world_mut()
+            .run_system_once(
+                move |mut effects_interface: EffectInterface<FasterTicksTestEffect>| {
+                    effects_interface.apply_effect(
+                        character_e,
+                        EffectSource::default(),
+                        FasterTicksTestEffect,
+                    )
+                },
+            )
+            .unwrap();
+        assert_eq!(tick_interval(&mut app), Some(Duration::from_millis(500)));
+
+        app.world_mut()
+            .run_system_once(
+                move |mut effects_interface: EffectInterface<FasterTicksTestEffect>| {
+                    effects_interface.remove_effect(effect_e);
+                },
+            )
+            .unwrap();
+        assert_eq!(tick_interval(&mut app), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_format_description() {
+        let stats = AbilityStats::new([
+            (AbilityStat::Damage, StatValue::Amount(5.0)),
+            (
+                AbilityStat::TickInterval,
+                StatValue::Duration(Duration::from_millis(500)),
+            ),
+            (
+                AbilityStat::OutgoingDamageMultiplier,
+                StatValue::Multiplier(0.85),
+            ),
+        ]);
+
+        assert_eq!(
+            stats.format_description(
+                "Deals {Damage} damage every {TickInterval}, at {OutgoingDamageMultiplier}."
+            ),
+            "Deals 5 damage every 0.5s, at 85%."
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::game_logic::{
+    ability_stats::{AbilityStat, AbilityStats},
+    damage_resolution::{AbsorbedDamage, DamageInstance, DamageResolutionStep, DamageType},
+    effects::{
+        EffectCategory, EffectClassification, EffectFromStats, EffectInterface, EffectTimer,
+        GameEffect, ReflectGameEffect, StackableEffect, StackingPolicy,
+    },
+};
+
+/// A pool of hit points that soaks incoming damage before it reaches the holder's health. The
+/// shield is removed once it is depleted, or when its [`EffectTimer`] finishes.
+///
+/// [`EffectTimer`]: crate::game_logic::effects::EffectTimer
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(GameEffect)]
+pub struct AbsorbShieldEffect {
+    pub remaining: f64,
+    /// Only damage of these types is absorbed, `None` absorbs damage of all types.
+    pub damage_types: Option<Vec<DamageType>>,
+}
+
+impl AbsorbShieldEffect {
+    /// A shield that absorbs damage of all types.
+    pub fn new(amount: f64) -> Self {
+        Self {
+            remaining: amount,
+            damage_types: None,
+        }
+    }
+
+    /// A shield that only absorbs damage of `damage_types`.
+    pub fn against(amount: f64, damage_types: impl IntoIterator<Item = DamageType>) -> Self {
+        Self {
+            remaining: amount,
+            damage_types: Some(damage_types.into_iter().collect()),
+        }
+    }
+
+    pub fn absorbs(&self, damage_type: DamageType) -> bool {
+        self.damage_types
+            .as_ref()
+            .is_none_or(|damage_types| damage_types.contains(&damage_type))
+    }
+}
+
+impl GameEffect for AbsorbShieldEffect {}
+
+impl StackableEffect for AbsorbShieldEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::IndependentPerSource;
+    const CLASSIFICATION: EffectClassification = EffectClassification::buff(EffectCategory::Magic);
+}
+
+/// Absorbs up to [`AbilityStat::AbsorbAmount`] damage of all types.
+impl EffectFromStats for AbsorbShieldEffect {
+    fn from_stats(stats: &AbilityStats) -> Option<(Self, EffectTimer)> {
+        Some((
+            AbsorbShieldEffect::new(stats.amount(AbilityStat::AbsorbAmount)?),
+            EffectTimer::duration(stats.duration(AbilityStat::EffectDuration)?),
+        ))
+    }
+}
+
+#[derive(SystemParam)]
+pub struct AbsorbShieldInterface<'w, 's> {
+    shields: EffectInterface<'w, 's, AbsorbShieldEffect>,
+    shield_effects: Query<'w, 's, &'static AbsorbShieldEffect>,
+}
+
+impl<'w, 's> AbsorbShieldInterface<'w, 's> {
+    /// Returns the sum of the remaining amounts of all shields of `character`.
+    pub fn total_shield(&self, character: Entity) -> f64 {
+        self.shield_effects
+            .iter_many(self.shields.get_effects(character))
+            .map(|shield| shield.remaining.max(0.0))
+            .sum()
+    }
+}
+
+/// Lets the shields of each target absorb its incoming damage, the oldest shield first, and
+/// removes depleted shields.
+pub fn absorb_damage_with_shields(
+    mut damage_instances: Query<(&mut DamageInstance, &mut AbsorbedDamage)>,
+    mut shield_effects: Query<&mut AbsorbShieldEffect>,
+    mut shields: EffectInterface<AbsorbShieldEffect>,
+) {
+    for (mut damage, mut absorbed) in &mut damage_instances {
+        let shield_es = shields.get_effects(damage.target).collect::<Vec<_>>();
+
+        for shield_e in shield_es {
+            if damage.amount <= 0.0 {
+                break;
+            }
+
+            let Ok(mut shield) = shield_effects.get_mut(shield_e) else {
+                continue;
+            };
+
+            // depleted shields are only despawned after this system
+            if shield.remaining <= 0.0 || !shield.absorbs(damage.damage_type) {
+                continue;
+            }
+
+            shield.remaining -= absorbed.absorb(&mut damage, shield.remaining);
+
+            if shield.remaining <= 0.0 {
+                shields.remove_effect(shield_e);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AbsorbShieldPlugin;
+
+impl Plugin for AbsorbShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AbsorbShieldEffect>().add_systems(
+            Update,
+            absorb_damage_with_shields.in_set(DamageResolutionStep::Absorb),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        ecs::{message::MessageCursor, system::RunSystemOnce},
+        prelude::*,
+    };
+
+    use super::{AbsorbShieldEffect, AbsorbShieldInterface, AbsorbShieldPlugin};
+    use crate::game_logic::{
+        commands::CommandsPlugin,
+        damage_resolution::{
+            DamageDealt, DamageInstance, DamageResolutionPlugin, DamageType, DealDamage,
+        },
+        effects::{EffectInterface, EffectSource, EffectsPlugin},
+        fight::FightPlugin,
+        health::Health,
+    };
+
+    fn hit(app: &mut App, target: Entity, amount: f64, damage_type: DamageType) {
+        app.world_mut().write_message(DealDamage(DamageInstance {
+            source: None,
+            source_ability: None,
+            target,
+            amount,
+            damage_type,
+        }));
+    }
+
+    fn total_shield(app: &mut App, target: Entity) -> f64 {
+        app.world_mut()
+            .run_system_once(move |interface: AbsorbShieldInterface| interface.total_shield(target))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_shield_absorbs_matching_damage_until_depleted() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .add_plugins(DamageResolutionPlugin)
+            .add_plugins(AbsorbShieldPlugin);
+
+        let target = app.world_mut().spawn(Health::new(100.0)).id();
+
+        app.world_mut()
+            .run_system_once(
+                move |mut effects_interface: EffectInterface<AbsorbShieldEffect>| {
+                    effects_interface.apply_effect(
+                        target,
+                        EffectSource::default(),
+                        AbsorbShieldEffect::against(30.0, [DamageType::Magical]),
+                    );
+                },
+            )
+            .unwrap();
+
+        // physical damage passes the shield
+        hit(&mut app, target, 10.0, DamageType::Physical);
+        hit(&mut app, target, 20.0, DamageType::Magical);
+        app.update();
+
+        assert_eq!(app.world().get::<Health>(target).unwrap().current(), 90.0);
+        assert_eq!(total_shield(&mut app, target), 10.0);
+
+        hit(&mut app, target, 25.0, DamageType::Magical);
+        app.update();
+
+        assert_eq!(app.world().get::<Health>(target).unwrap().current(), 75.0);
+        assert_eq!(total_shield(&mut app, target), 0.0);
+
+        let messages = app.world().resource::<Messages<DamageDealt>>();
+        let absorbed = MessageCursor::default()
+            .read(messages)
+            .map(|dealt: &DamageDealt| dealt.absorbed)
+            .collect::<Vec<_>>();
+        assert_eq!(absorbed, [0.0, 20.0, 10.0]);
+    }
+}
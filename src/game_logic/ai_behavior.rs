@@ -291,6 +291,7 @@ mod tests {
                 assert_eq!(use_ability.caster_e, enemy_e);
                 assert_eq!(use_ability.target, Some(caster_e));
             }
+            other => panic!("AI should have submitted UseAbility, got {other:?}"),
         }
     }
 }
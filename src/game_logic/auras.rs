@@ -0,0 +1,276 @@
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+
+use crate::{
+    game_logic::{
+        ability::Ability,
+        effects::{EffectInterface, EffectSource, StackableEffect},
+        faction::Faction,
+        health::Health,
+    },
+    utils::holds_held::Held,
+};
+
+/// Which characters of the fight an [`Aura`] affects, relative to the faction of its source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum AuraTargets {
+    /// All friendly characters, including the source itself.
+    Allies,
+    /// All hostile characters.
+    Enemies,
+}
+
+impl AuraTargets {
+    pub fn affects(&self, source_faction: &Faction, target_faction: &Faction) -> bool {
+        match self {
+            AuraTargets::Allies => source_faction.is_friendly(target_faction),
+            AuraTargets::Enemies => source_faction.is_enemy(target_faction),
+        }
+    }
+}
+
+/// Emits `effect` to all [`AuraTargets`] in the fight of the character holding this component.
+///
+/// The effect is applied without [`EffectTimer`] and persists on its targets only as long as the
+/// aura is active: it is removed when its source dies, is despawned or loses the aura, and - if
+/// set - no longer holds `required_ability`. It is re-applied when the source is resurrected or
+/// the effect was removed otherwise, so aura effects have to be
+/// [`EffectClassification::undispellable`], which [`sync_auras`] checks at compile time.
+///
+/// Add [`sync_auras`] as system for each aura effect type. Aura effects should use
+/// [`StackingPolicy::IndependentPerSource`], so auras of different sources don't interfere.
+///
+/// [`EffectClassification::undispellable`]: crate::game_logic::effects::EffectClassification::undispellable
+/// [`EffectTimer`]: crate::game_logic::effects::EffectTimer
+/// [`StackingPolicy::IndependentPerSource`]: crate::game_logic::effects::StackingPolicy::IndependentPerSource
+#[derive(Debug, Clone, Component)]
+pub struct Aura<E: StackableEffect + Clone> {
+    pub effect: E,
+    pub targets: AuraTargets,
+    /// The ability (or stance) the source has to hold for the aura to be active. Also recorded
+    /// as the [`EffectSource::ability`] of the applied effects.
+    pub required_ability: Option<Entity>,
+}
+
+/// Marks an effect entity as applied by the [`Aura`] of `source`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Reflect)]
+pub struct AuraEffect {
+    pub source: Entity,
+}
+
+/// Applies the effects of all active [`Aura`]s with effect `E` to their current targets, and
+/// removes them from characters that are no longer targeted, or whose aura ended.
+pub fn sync_auras<E: StackableEffect + Clone>(
+    auras: Query<(Entity, &Aura<E>, &ChildOf, &Faction, &Health)>,
+    aura_effects: Query<(Entity, &AuraEffect), With<E>>,
+    fight_children: Query<&Children>,
+    factions: Query<&Faction>,
+    held_abilities: Query<&Held<Ability>>,
+    mut effects: EffectInterface<E>,
+    mut commands: Commands,
+) {
+    const {
+        assert!(
+            !E::CLASSIFICATION.dispellable,
+            "dispelled aura effects would be re-applied right away"
+        );
+    }
+
+    let targets_by_source = auras
+        .iter()
+        .filter(|(source_e, aura, _, _, health)| {
+            health.is_alive()
+                && aura.required_ability.is_none_or(|ability_e| {
+                    held_abilities.related::<Held<Ability>>(ability_e) == Some(*source_e)
+                })
+        })
+        .map(|(source_e, aura, child_of, source_faction, _)| {
+            let targets = fight_children
+                .get(child_of.parent())
+                .map(|children| &children[..])
+                .unwrap_or_default()
+                .iter()
+                .copied()
+                .filter(|&target_e| {
+                    factions.get(target_e).is_ok_and(|target_faction| {
+                        aura.targets.affects(source_faction, target_faction)
+                    })
+                })
+                .collect::<HashSet<_>>();
+
+            (source_e, targets)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut already_applied = HashSet::new();
+
+    for (effect_e, aura_effect) in &aura_effects {
+        let target_e = effects.get_target_of_effect(effect_e);
+
+        let is_targeted = targets_by_source
+            .get(&aura_effect.source)
+            .is_some_and(|targets| targets.contains(&target_e));
+
+        if is_targeted {
+            already_applied.insert((aura_effect.source, target_e));
+        } else {
+            effects.remove_effect(effect_e);
+        }
+    }
+
+    for (source_e, targets) in targets_by_source {
+        let Ok((_, aura, ..)) = auras.get(source_e) else {
+            continue;
+        };
+
+        for target_e in targets {
+            if already_applied.contains(&(source_e, target_e)) {
+                continue;
+            }
+
+            let source = EffectSource {
+                caster: Some(source_e),
+                ability: aura.required_ability,
+            };
+            let effect_e = effects.apply_effect(target_e, source, aura.effect.clone());
+            commands
+                .entity(effect_e)
+                .insert(AuraEffect { source: source_e });
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AurasPlugin;
+
+impl Plugin for AurasPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AuraTargets>()
+            .register_type::<AuraEffect>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{ecs::system::RunSystemOnce, prelude::*, time::TimeUpdateStrategy};
+
+    use super::{Aura, AuraEffect, AuraTargets, AurasPlugin, sync_auras};
+    use crate::{
+        PerUpdateSet,
+        game_logic::{
+            commands::CommandsPlugin,
+            effects::{
+                EffectCategory, EffectClassification, EffectInterface, EffectsPlugin, GameEffect,
+                StackableEffect, StackingPolicy,
+            },
+            fight::FightPlugin,
+            health::HealthInterface,
+        },
+        test_utils::{TestFightEntities, spawn_test_fight},
+    };
+
+    #[derive(Debug, Clone, Component, Reflect)]
+    struct TestAuraEffect;
+
+    impl GameEffect for TestAuraEffect {}
+
+    impl StackableEffect for TestAuraEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::IndependentPerSource;
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::debuff(EffectCategory::Magic).undispellable();
+    }
+
+    fn aura_targets(app: &mut App, characters: [Entity; 2]) -> Vec<Entity> {
+        app.world_mut()
+            .run_system_once(move |effects: EffectInterface<TestAuraEffect>| {
+                characters
+                    .into_iter()
+                    .filter(|&character| effects.get_effect(character).is_some())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_aura_effects_follow_source_liveness_and_presence() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .add_plugins(AurasPlugin)
+            .add_systems(
+                FixedUpdate,
+                sync_auras::<TestAuraEffect>.in_set(PerUpdateSet::LogicUpdate),
+            );
+
+        let TestFightEntities {
+            caster_e, enemy_e, ..
+        } = spawn_test_fight(&mut app);
+
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+
+        app.world_mut().entity_mut(caster_e).insert(Aura {
+            effect: TestAuraEffect,
+            targets: AuraTargets::Enemies,
+            required_ability: None,
+        });
+        app.update();
+
+        assert_eq!(aura_targets(&mut app, [caster_e, enemy_e]), [enemy_e]);
+        let mut aura_effects = app.world_mut().query::<&AuraEffect>();
+        assert_eq!(
+            aura_effects.single(app.world()).unwrap(),
+            &AuraEffect { source: caster_e }
+        );
+
+        // the aura ends with the death of its source
+        app.world_mut()
+            .run_system_once(move |mut health: HealthInterface| {
+                health.lose_hp(caster_e, 1000.0).unwrap();
+            })
+            .unwrap();
+        app.update();
+
+        assert!(aura_targets(&mut app, [caster_e, enemy_e]).is_empty());
+
+        // and comes back with its resurrection
+        app.world_mut()
+            .run_system_once(move |mut health: HealthInterface| {
+                health.resurrect(caster_e, 50.0).unwrap();
+            })
+            .unwrap();
+        app.update();
+
+        assert_eq!(aura_targets(&mut app, [caster_e, enemy_e]), [enemy_e]);
+
+        // allied auras include the source
+        app.world_mut().entity_mut(enemy_e).insert(Aura {
+            effect: TestAuraEffect,
+            targets: AuraTargets::Allies,
+            required_ability: None,
+        });
+        app.update();
+
+        let mut effects = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestAuraEffect>>();
+        assert_eq!(effects.iter(app.world()).count(), 2);
+
+        app.world_mut().despawn(caster_e);
+        app.update();
+
+        assert_eq!(effects.iter(app.world()).count(), 1);
+        assert_eq!(
+            aura_effects.single(app.world()).unwrap(),
+            &AuraEffect { source: enemy_e }
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::time::Duration;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::game_logic::{
+    ability_stats::{AbilityStatModifier, AbilityStatModifiers, StatModifierKind},
+    effects::{GameEffect, HasEffects},
+};
+
+/// Named properties of a character that can be modified by effects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum CharacterStat {
+    /// Modifies the amount of damage the character deals.
+    OutgoingDamage,
+    /// Modifies the amount of damage the character takes.
+    IncomingDamage,
+    /// Modifies the cast time of the character's abilities, in seconds.
+    CastTime,
+    /// How fast the character's cooldowns recover, `1.0` being the normal rate.
+    CooldownRate,
+    /// Modifies the amount of healing the character receives.
+    HealingReceived,
+}
+
+impl CharacterStat {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CharacterStat::OutgoingDamage => "Damage Dealt",
+            CharacterStat::IncomingDamage => "Damage Taken",
+            CharacterStat::CastTime => "Cast Time",
+            CharacterStat::CooldownRate => "Cooldown Rate",
+            CharacterStat::HealingReceived => "Healing Received",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct CharacterStatModifier {
+    pub stat: CharacterStat,
+    pub kind: StatModifierKind,
+}
+
+/// Modifiers for the stats of a character. Can be on the character itself, or on one of its
+/// effect entities, in which case they are removed together with the effect.
+#[derive(Debug, Clone, Default, Component, Reflect)]
+pub struct CharacterStatModifiers(pub Vec<CharacterStatModifier>);
+
+/// An effect that modifies the stats of its holder, and of the holder's abilities.
+///
+/// Add [`insert_stat_modifiers`] as observer for each such effect, which keeps the
+/// [`CharacterStatModifiers`] and [`AbilityStatModifiers`] of the effect entity up to date.
+pub trait StatModifyingEffect: GameEffect + Component {
+    fn stat_modifiers(&self) -> Vec<CharacterStatModifier>;
+
+    fn ability_stat_modifiers(&self) -> Vec<AbilityStatModifier> {
+        Vec::new()
+    }
+}
+
+/// Observer that inserts the [`CharacterStatModifiers`] and [`AbilityStatModifiers`] of a
+/// [`StatModifyingEffect`] on its effect entity whenever the effect is inserted or replaced.
+pub fn insert_stat_modifiers<E: StatModifyingEffect>(
+    trigger: On<Insert, E>,
+    effects: Query<&E>,
+    mut commands: Commands,
+) {
+    let Ok(effect) = effects.get(trigger.entity) else {
+        return;
+    };
+
+    commands.entity(trigger.entity).insert((
+        CharacterStatModifiers(effect.stat_modifiers()),
+        AbilityStatModifiers(effect.ability_stat_modifiers()),
+    ));
+}
+
+#[derive(SystemParam)]
+pub struct CharacterStatsInterface<'w, 's> {
+    has_effects: Query<'w, 's, &'static HasEffects>,
+    modifiers: Query<'w, 's, &'static CharacterStatModifiers>,
+}
+
+impl<'w, 's> CharacterStatsInterface<'w, 's> {
+    /// Applies all modifiers of `stat` of `character` to `base`: first all additive modifiers
+    /// are summed up, then the result is multiplied with all multiplicative modifiers. The result
+    /// is never negative.
+    pub fn modified(&self, character: Entity, stat: CharacterStat, base: f64) -> f64 {
+        let (add, multiply) = self
+            .modifiers_of(character)
+            .filter(|m| m.stat == stat)
+            .fold((0.0, 1.0), |(add, multiply), m| match m.kind {
+                StatModifierKind::Add(a) => (add + a, multiply),
+                StatModifierKind::Multiply(mul) => (add, multiply * mul),
+            });
+
+        ((base + add) * multiply).max(0.0)
+    }
+
+    /// Like [`Self::modified()`], for stats that are durations in seconds.
+    pub fn modified_duration(
+        &self,
+        character: Entity,
+        stat: CharacterStat,
+        base: Duration,
+    ) -> Duration {
+        Duration::from_secs_f64(self.modified(character, stat, base.as_secs_f64()))
+    }
+
+    fn modifiers_of(&self, character: Entity) -> impl Iterator<Item = &CharacterStatModifier> {
+        let effect_entities = self
+            .has_effects
+            .get(character)
+            .map(HasEffects::effects)
+            .unwrap_or_default();
+
+        self.modifiers
+            .get(character)
+            .into_iter()
+            .chain(self.modifiers.iter_many(effect_entities))
+            .flat_map(|modifiers| &modifiers.0)
+    }
+}
+
+#[derive(Debug)]
+pub struct CharacterStatsPlugin;
+
+impl Plugin for CharacterStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CharacterStat>()
+            .register_type::<CharacterStatModifier>()
+            .register_type::<CharacterStatModifiers>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+    use super::{
+        CharacterStat, CharacterStatModifier, CharacterStatModifiers, CharacterStatsInterface,
+        CharacterStatsPlugin, StatModifyingEffect, insert_stat_modifiers,
+    };
+    use crate::game_logic::{
+        ability_stats::StatModifierKind,
+        commands::CommandsPlugin,
+        damage_resolution::{DamageInstance, DamageResolutionPlugin, DamageType, DealDamage},
+        effects::{
+            EffectCategory, EffectClassification, EffectInterface, EffectSource, EffectsPlugin,
+            GameEffect, StackableEffect, StackingPolicy,
+        },
+        fight::FightPlugin,
+        health::Health,
+    };
+
+    #[derive(Debug, Component, Reflect)]
+    struct DamageTakenTestEffect;
+
+    impl GameEffect for DamageTakenTestEffect {}
+
+    impl StackableEffect for DamageTakenTestEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::Replace;
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::debuff(EffectCategory::Magic);
+    }
+
+    impl StatModifyingEffect for DamageTakenTestEffect {
+        fn stat_modifiers(&self) -> Vec<CharacterStatModifier> {
+            vec![CharacterStatModifier {
+                stat: CharacterStat::IncomingDamage,
+                kind: StatModifierKind::Multiply(2.0),
+            }]
+        }
+    }
+
+    #[test]
+    fn test_modifiers_of_character_and_effects_are_aggregated() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .add_plugins(CharacterStatsPlugin)
+            .add_plugins(DamageResolutionPlugin)
+            .add_observer(insert_stat_modifiers::<DamageTakenTestEffect>);
+
+        let attacker = app
+            .world_mut()
+            .spawn(CharacterStatModifiers(vec![CharacterStatModifier {
+                stat: CharacterStat::OutgoingDamage,
+                kind: StatModifierKind::Add(5.0),
+            }]))
+            .id();
+        let target = app
+            .world_mut()
+            .spawn((
+                Health::new(100.0),
+                CharacterStatModifiers(vec![CharacterStatModifier {
+                    stat: CharacterStat::IncomingDamage,
+                    kind: StatModifierKind::Add(1.0),
+                }]),
+            ))
+            .id();
+
+        app.world_mut()
+            .run_system_once(
+                move |mut effects_interface: EffectInterface<DamageTakenTestEffect>| {
+                    effects_interface.apply_effect(
+                        target,
+                        EffectSource::default(),
+                        DamageTakenTestEffect,
+                    );
+                },
+            )
+            .unwrap();
+
+        let incoming = app
+            .world_mut()
+            .run_system_once(move |interface: CharacterStatsInterface| {
+                interface.modified(target, CharacterStat::IncomingDamage, 10.0)
+            })
+            .unwrap();
+        assert_eq!(incoming, 22.0);
+
+        // (10 + 5) outgoing, then (15 + 1) * 2 incoming
+        app.world_mut().write_message(DealDamage(DamageInstance {
+            source: Some(attacker),
+            source_ability: None,
+            target,
+            amount: 10.0,
+            damage_type: DamageType::Physical,
+        }));
+        app.update();
+
+        assert_eq!(app.world().get::<Health>(target).unwrap().current(), 68.0);
+    }
+}
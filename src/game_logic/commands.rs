@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use derive_more::From;
 
-use crate::game_logic::ability_casting::UseAbility;
+use crate::game_logic::ability_casting::{CancelCast, UseAbility};
 
 #[derive(Event, Message, Debug, Clone)]
 pub struct GameCommand {
@@ -28,12 +28,14 @@ pub enum GameCommandSource {
 #[derive(Debug, Clone, From)]
 pub enum GameCommandKind {
     UseAbility(UseAbility),
+    CancelCast(CancelCast),
 }
 
 impl GameCommandKind {
     pub fn get_fight_e(&self) -> Option<Entity> {
         match self {
             GameCommandKind::UseAbility(use_ability) => Some(use_ability.fight_e),
+            GameCommandKind::CancelCast(cancel_cast) => Some(cancel_cast.fight_e),
         }
     }
 }
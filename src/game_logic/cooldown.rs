@@ -1,20 +1,30 @@
 use std::time::Duration;
 
-use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy::{ecs::system::SystemParam, platform::collections::HashMap, prelude::*};
+use serde::Deserialize;
 
 use crate::{
     PerUpdateSet,
     game_logic::{
-        ability::Ability,
+        ability::{
+            Ability, AbilityCooldown, AbilityCooldownGroup, CooldownGroup, OnGlobalCooldown,
+            ResetCooldownOnKill,
+        },
         ability_slots::AbilitySlot,
+        character_stats::{CharacterStat, CharacterStatsInterface},
+        damage_resolution::{DamageDealt, DamageResolutionStep},
         fight::{FightInterface, FightTime},
+        health::Health,
     },
-    utils::holds_held::Held,
+    utils::holds_held::{Held, Holds},
 };
 
 #[derive(Debug, Component, Reflect)]
 pub struct Cooldown {
     cooldown_timer: Timer,
+    /// The ability whose effect this cooldown waits for instead, once one is applied, see
+    /// [`CooldownStart::EffectExpiry`]
+    awaiting_effect_of: Option<Entity>,
 }
 
 impl Cooldown {
@@ -23,6 +33,7 @@ impl Cooldown {
     pub fn new(cooldown_duration: Duration) -> Cooldown {
         Cooldown {
             cooldown_timer: Timer::new(cooldown_duration, TimerMode::Once),
+            awaiting_effect_of: None,
         }
     }
 
@@ -30,43 +41,364 @@ impl Cooldown {
     pub fn remaining_cooldown(&self) -> Duration {
         self.cooldown_timer.remaining()
     }
+
+    fn reduce(&mut self, amount: Duration) {
+        self.cooldown_timer.tick(amount);
+    }
+
+    fn extend(&mut self, amount: Duration) {
+        let duration = self.cooldown_timer.duration() + amount;
+        self.cooldown_timer.set_duration(duration);
+    }
+}
+
+/// When the cooldown of an ability or slot starts, relative to the cast using it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Deserialize)]
+pub enum CooldownStart {
+    /// When the cast starts, e.g., for abilities that commit on start. Aborting the cast doesn't
+    /// undo the cooldown.
+    CastStart,
+    /// When the cast finishes successfully.
+    #[default]
+    CastFinish,
+    /// When an effect applied by the ability expires or is removed, e.g., for buffs whose cooldown
+    /// should only start once they ended. Until then, the ability or slot counts as on cooldown.
+    /// Starts when the cast finishes instead, if the ability applies no effect, e.g., because the
+    /// target is immune.
+    EffectExpiry,
+}
+
+/// The cooldown an interrupted cast starts, if its cooldown didn't start already.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect, Deserialize)]
+pub enum InterruptedCooldown {
+    #[default]
+    None,
+    /// The fraction (`0.0` to `1.0`) of the cooldown.
+    Partial(f64),
+    Full,
+}
+
+/// Configures when the cooldown of the ability or slot it is on starts. Without it, cooldowns
+/// start when the cast finishes, and interrupted casts start no cooldown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Component, Reflect, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CooldownTiming {
+    #[serde(default)]
+    pub start: CooldownStart,
+    #[serde(default)]
+    pub on_interrupt: InterruptedCooldown,
+}
+
+/// Set on an ability or slot whose cooldown starts once an effect applied by `ability_e` ended,
+/// see [`CooldownStart::EffectExpiry`].
+#[derive(Debug, Clone, Copy, Component, Reflect)]
+pub struct CooldownAwaitingEffectExpiry {
+    pub ability_e: Entity,
+}
+
+/// Triggered on an ability or slot when its [`Cooldown`] finished, i.e., ran out or was reset,
+/// right after the [`Cooldown`] was removed.
+#[derive(Debug, Clone, EntityEvent)]
+pub struct CooldownFinished {
+    #[event_target]
+    pub entity: Entity,
+    /// The character holding the ability or slot.
+    pub character: Option<Entity>,
+}
+
+/// Lets an ability be used up to `max_charges` times in a row. Each use spends a charge when the
+/// cast finishes, and spent charges are regained one at a time, every `recharge_duration`. The
+/// ability can't be used while it has no charges left. An additional [`AbilityCooldown`] still
+/// applies after each use, e.g. to enforce a minimum time between uses.
+///
+/// [`AbilityCooldown`]: crate::game_logic::ability::AbilityCooldown
+#[derive(Debug, Clone, Component, Reflect)]
+pub struct AbilityCharges {
+    max_charges: u32,
+    current_charges: u32,
+    recharge_timer: Timer,
+}
+
+impl AbilityCharges {
+    /// Creates the charges of an ability, with all charges available.
+    pub fn new(max_charges: u32, recharge_duration: Duration) -> Self {
+        Self {
+            max_charges,
+            current_charges: max_charges,
+            recharge_timer: Timer::new(recharge_duration, TimerMode::Repeating),
+        }
+    }
+
+    pub fn max_charges(&self) -> u32 {
+        self.max_charges
+    }
+
+    pub fn current_charges(&self) -> u32 {
+        self.current_charges
+    }
+
+    pub fn recharge_duration(&self) -> Duration {
+        self.recharge_timer.duration()
+    }
+
+    pub fn has_charge(&self) -> bool {
+        self.current_charges > 0
+    }
+
+    /// Returns the time until the next charge is regained, `None` if all charges are available.
+    pub fn time_to_next_charge(&self) -> Option<Duration> {
+        (self.current_charges < self.max_charges).then(|| self.recharge_timer.remaining())
+    }
+
+    /// Spends a charge. Returns `false` if there was none left.
+    pub fn spend(&mut self) -> bool {
+        if !self.has_charge() {
+            return false;
+        }
+
+        // the recharge only starts once a charge is missing
+        if self.current_charges == self.max_charges {
+            self.recharge_timer.reset();
+        }
+
+        self.current_charges -= 1;
+        true
+    }
+
+    fn recharge(&mut self, delta: Duration) {
+        if self.current_charges >= self.max_charges {
+            return;
+        }
+
+        self.recharge_timer.tick(delta);
+        self.current_charges = (self.current_charges
+            + self.recharge_timer.times_finished_this_tick())
+        .min(self.max_charges);
+
+        if self.current_charges == self.max_charges {
+            self.recharge_timer.reset();
+        }
+    }
+}
+
+/// The global cooldown of a character. Using an ability with [`OnGlobalCooldown`] starts an
+/// [`ActiveGlobalCooldown`] for `duration`, during which no other ability with [`OnGlobalCooldown`]
+/// can be used. Characters without it have no global cooldown.
+#[derive(Debug, Clone, Component, Reflect)]
+pub struct GlobalCooldown {
+    pub duration: Duration,
+}
+
+/// The running [`GlobalCooldown`] of a character, removed once it finished.
+#[derive(Debug, Component, Reflect)]
+pub struct ActiveGlobalCooldown {
+    cooldown_timer: Timer,
+}
+
+impl ActiveGlobalCooldown {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            cooldown_timer: Timer::new(duration, TimerMode::Once),
+        }
+    }
+
+    /// Returns the duration for which the global cooldown is still running
+    pub fn remaining_cooldown(&self) -> Duration {
+        self.cooldown_timer.remaining()
+    }
+
+    /// Returns the fraction (`1.0` to `0.0`) of the global cooldown that is still running
+    pub fn fraction_remaining(&self) -> f32 {
+        self.cooldown_timer.fraction_remaining()
+    }
+}
+
+/// The running cooldowns of the [`CooldownGroup`]s of a character. Inserted when an ability with
+/// an [`AbilityCooldownGroup`] is used for the first time.
+#[derive(Debug, Default, Component, Reflect)]
+pub struct GroupCooldowns {
+    cooldowns: HashMap<CooldownGroup, Timer>,
 }
 
+impl GroupCooldowns {
+    /// Returns the duration for which the cooldown of `group` is still running, `None` if it isn't
+    pub fn remaining_cooldown(&self, group: &CooldownGroup) -> Option<Duration> {
+        self.cooldowns.get(group).map(Timer::remaining)
+    }
+
+    /// Starts the cooldown of `group`, unless it is already running for longer
+    pub fn start(&mut self, group: CooldownGroup, duration: Duration) {
+        if self
+            .remaining_cooldown(&group)
+            .is_none_or(|remaining| remaining < duration)
+        {
+            self.cooldowns
+                .insert(group, Timer::new(duration, TimerMode::Once));
+        }
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        self.cooldowns.retain(|_, cooldown_timer| {
+            cooldown_timer.tick(delta);
+            !cooldown_timer.is_finished()
+        });
+    }
+}
+
+/// Ticks all [`Cooldown`]s of characters in unpaused fights, scaled by the character's
+/// [`CharacterStat::CooldownRate`]. Finished cooldowns are removed, triggering
+/// [`CooldownFinished`].
 fn tick_cooldowns(
     cooldowns: Query<(Entity, &mut Cooldown), Without<FightTime>>,
+    // not via `CooldownInterface`, which reads the `Cooldown`s
+    held_abilities: Query<&Held<Ability>>,
+    held_ability_slots: Query<&Held<AbilitySlot>>,
     time: Res<Time>,
-    cooldown_interface: CooldownInterface,
     fight_interface: FightInterface,
+    character_stats: CharacterStatsInterface,
     mut commands: Commands,
 ) {
     let delta = time.delta();
 
     for (e, mut cooldown) in cooldowns {
-        let character = cooldown_interface.find_character_of_cooldown(e).unwrap();
+        let character = held_abilities
+            .related(e)
+            .or(held_ability_slots.related(e))
+            .unwrap();
         let fight_e = fight_interface.get_fight_of_entity(character);
 
         if fight_interface.is_fight_paused(fight_e) {
             continue;
         }
 
+        // cooldowns that were reduced (see `CooldownInterface`) might already be finished
+        let rate = character_stats.modified(character, CharacterStat::CooldownRate, 1.0);
+        cooldown.cooldown_timer.tick(delta.mul_f64(rate));
+
         if cooldown.cooldown_timer.is_finished() {
-            warn!(
-                "finished Cooldown exists on entity '{e}', but should already be removed. removing it now."
-            );
+            commands.entity(e).remove::<Cooldown>();
+            commands.trigger(CooldownFinished {
+                entity: e,
+                character: Some(character),
+            });
         }
+    }
+}
+
+/// Recharges the [`AbilityCharges`] of characters in unpaused fights, with the same rate as
+/// their [`Cooldown`]s.
+fn tick_ability_charges(
+    ability_charges: Query<(Entity, &mut AbilityCharges)>,
+    time: Res<Time>,
+    cooldown_interface: CooldownInterface,
+    fight_interface: FightInterface,
+    character_stats: CharacterStatsInterface,
+) {
+    let delta = time.delta();
 
-        cooldown.cooldown_timer.tick(delta);
+    for (ability_e, mut charges) in ability_charges {
+        let Some(character) = cooldown_interface.find_character_of_cooldown(ability_e) else {
+            continue;
+        };
 
-        if cooldown.cooldown_timer.is_finished() {
-            commands.entity(e).remove::<Cooldown>();
+        if fight_interface.is_fight_paused(fight_interface.get_fight_of_entity(character)) {
+            continue;
         }
+
+        let rate = character_stats.modified(character, CharacterStat::CooldownRate, 1.0);
+        charges.recharge(delta.mul_f64(rate));
     }
 }
 
+/// Ticks the [`ActiveGlobalCooldown`]s of characters in unpaused fights, with the same rate as
+/// their [`Cooldown`]s.
+fn tick_global_cooldowns(
+    global_cooldowns: Query<(Entity, &mut ActiveGlobalCooldown)>,
+    time: Res<Time>,
+    fight_interface: FightInterface,
+    character_stats: CharacterStatsInterface,
+    mut commands: Commands,
+) {
+    let delta = time.delta();
+
+    for (character, mut global_cooldown) in global_cooldowns {
+        if fight_interface.is_fight_paused(fight_interface.get_fight_of_entity(character)) {
+            continue;
+        }
+
+        let rate = character_stats.modified(character, CharacterStat::CooldownRate, 1.0);
+        global_cooldown.cooldown_timer.tick(delta.mul_f64(rate));
+
+        if global_cooldown.cooldown_timer.is_finished() {
+            commands.entity(character).remove::<ActiveGlobalCooldown>();
+        }
+    }
+}
+
+/// Ticks the [`GroupCooldowns`] of characters in unpaused fights, with the same rate as their
+/// [`Cooldown`]s.
+fn tick_group_cooldowns(
+    group_cooldowns: Query<(Entity, &mut GroupCooldowns)>,
+    time: Res<Time>,
+    fight_interface: FightInterface,
+    character_stats: CharacterStatsInterface,
+) {
+    let delta = time.delta();
+
+    for (character, mut group_cooldowns) in group_cooldowns {
+        if fight_interface.is_fight_paused(fight_interface.get_fight_of_entity(character)) {
+            continue;
+        }
+
+        let rate = character_stats.modified(character, CharacterStat::CooldownRate, 1.0);
+        group_cooldowns.tick(delta.mul_f64(rate));
+    }
+}
+
+/// Resets the cooldowns of [`ResetCooldownOnKill`] abilities whose damage killed the target
+fn reset_cooldowns_on_kill(
+    mut damage_dealt_events: MessageReader<DamageDealt>,
+    abilities: Query<(), With<ResetCooldownOnKill>>,
+    healths: Query<&Health>,
+    mut cooldown_interface: CooldownInterface,
+) {
+    for damage_dealt in damage_dealt_events.read() {
+        let Some(ability_e) = damage_dealt.instance.source_ability else {
+            continue;
+        };
+
+        // `DamageDealt` isn't sent for targets that were already dead
+        if abilities.contains(ability_e)
+            && healths
+                .get(damage_dealt.instance.target)
+                .is_ok_and(Health::is_dead)
+        {
+            cooldown_interface.reset_cooldown(ability_e);
+        }
+    }
+}
+
+/// Queries and modifies the cooldowns of abilities, slots and characters.
+///
+/// Running [`Cooldown`]s are modified via [`Commands`], so all modifications within the same
+/// system are based on the cooldowns at the start of it.
 #[derive(SystemParam)]
 pub struct CooldownInterface<'w, 's> {
     held_abilities: Query<'w, 's, &'static Held<Ability>>,
     held_ability_slots: Query<'w, 's, &'static Held<AbilitySlot>>,
+    holds_abilities: Query<'w, 's, &'static Holds<Ability>>,
+    holds_ability_slots: Query<'w, 's, &'static Holds<AbilitySlot>>,
+    cooldowns: Query<'w, 's, (Entity, &'static Cooldown)>,
+    ability_cooldowns: Query<'w, 's, &'static AbilityCooldown>,
+    ability_slots: Query<'w, 's, &'static AbilitySlot>,
+    cooldown_timings: Query<'w, 's, &'static CooldownTiming>,
+    awaiting_effect_expiry: Query<'w, 's, (Entity, &'static CooldownAwaitingEffectExpiry)>,
+    on_global_cooldown: Query<'w, 's, Has<OnGlobalCooldown>>,
+    global_cooldowns: Query<'w, 's, &'static GlobalCooldown>,
+    active_global_cooldowns: Query<'w, 's, &'static ActiveGlobalCooldown>,
+    cooldown_groups: Query<'w, 's, &'static AbilityCooldownGroup>,
+    group_cooldowns: Query<'w, 's, &'static GroupCooldowns>,
+    commands: Commands<'w, 's>,
 }
 
 impl<'w, 's> CooldownInterface<'w, 's> {
@@ -78,6 +410,289 @@ impl<'w, 's> CooldownInterface<'w, 's> {
             .related(entity)
             .or(self.held_ability_slots.related(entity))
     }
+
+    /// Returns the duration for which the [`Cooldown`] of an ability or slot is still running,
+    /// `None` if it isn't on cooldown
+    pub fn remaining_cooldown(&self, entity: Entity) -> Option<Duration> {
+        self.cooldowns
+            .get(entity)
+            .ok()
+            .map(|(_, cooldown)| cooldown.remaining_cooldown())
+    }
+
+    /// Returns all abilities and slots of `character` that are on cooldown, with their remaining
+    /// cooldown
+    pub fn cooldowns_of(&self, character: Entity) -> impl Iterator<Item = (Entity, Duration)> {
+        self.holds_abilities
+            .relationship_sources(character)
+            .chain(self.holds_ability_slots.relationship_sources(character))
+            .filter_map(|entity| Some((entity, self.remaining_cooldown(entity)?)))
+    }
+
+    /// Whether an ability or slot is on cooldown, or waits for its cooldown to start, see
+    /// [`CooldownStart::EffectExpiry`]
+    pub fn is_on_cooldown(&self, entity: Entity) -> bool {
+        self.cooldowns.contains(entity) || self.awaiting_effect_expiry.contains(entity)
+    }
+
+    /// Returns the duration of the cooldown an ability or slot starts when it is used, i.e., its
+    /// [`AbilityCooldown`] or [`AbilitySlot::on_use_cooldown`]
+    pub fn configured_cooldown(&self, entity: Entity) -> Option<Duration> {
+        self.ability_cooldowns
+            .get(entity)
+            .map(|cooldown| cooldown.duration)
+            .ok()
+            .or_else(|| self.ability_slots.get(entity).ok()?.on_use_cooldown)
+    }
+
+    /// Returns when the cooldown of an ability or slot starts
+    pub fn cooldown_timing(&self, entity: Entity) -> CooldownTiming {
+        self.cooldown_timings
+            .get(entity)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Starts a [`Cooldown`] for an ability or slot, replacing a running one
+    pub fn start_cooldown(&mut self, entity: Entity, duration: Duration) {
+        self.commands.entity(entity).insert(Cooldown::new(duration));
+    }
+
+    /// Starts the configured cooldown of an ability or slot used by a cast of `ability_e` that just
+    /// reached `reached` (its start or finish), if its [`CooldownTiming`] starts it there. With
+    /// [`CooldownStart::EffectExpiry`], the finish starts it as well, but it is deferred until the
+    /// effect ended, once `ability_e` applies one, see
+    /// [`Self::defer_cooldowns_until_effect_expiry`].
+    pub fn start_cooldown_at(&mut self, entity: Entity, ability_e: Entity, reached: CooldownStart) {
+        let Some(duration) = self.configured_cooldown(entity) else {
+            return;
+        };
+
+        match (self.cooldown_timing(entity).start, reached) {
+            (CooldownStart::CastStart, CooldownStart::CastStart)
+            | (CooldownStart::CastFinish, CooldownStart::CastFinish) => {
+                self.start_cooldown(entity, duration);
+            }
+            (CooldownStart::EffectExpiry, CooldownStart::CastFinish) => {
+                let mut cooldown = Cooldown::new(duration);
+                cooldown.awaiting_effect_of = Some(ability_e);
+                self.commands.entity(entity).insert(cooldown);
+            }
+            _ => {}
+        }
+    }
+
+    /// Replaces the cooldowns started by casts of `ability_e` that should start once its effect
+    /// ended, with a [`CooldownAwaitingEffectExpiry`], after `ability_e` applied an effect
+    pub fn defer_cooldowns_until_effect_expiry(&mut self, ability_e: Entity) {
+        let entities = self
+            .cooldowns
+            .iter()
+            .filter(|(_, cooldown)| cooldown.awaiting_effect_of == Some(ability_e))
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+
+        for entity in entities {
+            self.commands
+                .entity(entity)
+                .remove::<Cooldown>()
+                .insert(CooldownAwaitingEffectExpiry { ability_e });
+        }
+    }
+
+    /// Starts the cooldowns that waited for an effect of `ability_e` to end
+    pub fn start_cooldowns_awaiting_effect_expiry(&mut self, ability_e: Entity) {
+        let entities = self
+            .awaiting_effect_expiry
+            .iter()
+            .filter(|(_, awaiting)| awaiting.ability_e == ability_e)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+
+        for entity in entities {
+            self.commands
+                .entity(entity)
+                .remove::<CooldownAwaitingEffectExpiry>();
+
+            if let Some(duration) = self.configured_cooldown(entity) {
+                self.start_cooldown(entity, duration);
+            }
+        }
+    }
+
+    /// Starts the [`CooldownTiming::on_interrupt`] cooldown of an ability or slot used by an
+    /// interrupted cast, unless its cooldown already started with the cast
+    pub fn start_cooldown_on_interrupt(&mut self, entity: Entity) {
+        let Some(duration) = self.configured_cooldown(entity) else {
+            return;
+        };
+
+        let timing = self.cooldown_timing(entity);
+        if timing.start == CooldownStart::CastStart {
+            return;
+        }
+
+        match timing.on_interrupt {
+            InterruptedCooldown::None => {}
+            InterruptedCooldown::Partial(fraction) => {
+                self.start_cooldown(entity, duration.mul_f64(fraction.clamp(0.0, 1.0)));
+            }
+            InterruptedCooldown::Full => self.start_cooldown(entity, duration),
+        }
+    }
+
+    /// Reduces the running cooldown of an ability or slot by `amount`, finishing it if nothing
+    /// remains. Returns `false` if it isn't on cooldown.
+    pub fn reduce_cooldown(&mut self, entity: Entity, amount: Duration) -> bool {
+        let Some(remaining) = self.remaining_cooldown(entity) else {
+            return false;
+        };
+
+        if amount >= remaining {
+            self.finish_cooldown(entity);
+        } else {
+            self.commands
+                .entity(entity)
+                .entry::<Cooldown>()
+                .and_modify(move |mut cooldown| cooldown.reduce(amount));
+        }
+
+        true
+    }
+
+    /// Reduces the running cooldown of an ability or slot by `fraction` (`0.0` to `1.0`) of its
+    /// remaining duration, e.g., `0.5` halves it. Returns `false` if it isn't on cooldown.
+    pub fn reduce_cooldown_by_fraction(&mut self, entity: Entity, fraction: f64) -> bool {
+        let Some(remaining) = self.remaining_cooldown(entity) else {
+            return false;
+        };
+
+        self.reduce_cooldown(entity, remaining.mul_f64(fraction.clamp(0.0, 1.0)))
+    }
+
+    /// Extends the running cooldown of an ability or slot by `amount`. Returns `false` if it isn't
+    /// on cooldown.
+    pub fn extend_cooldown(&mut self, entity: Entity, amount: Duration) -> bool {
+        if self.remaining_cooldown(entity).is_none() {
+            return false;
+        }
+
+        self.commands
+            .entity(entity)
+            .entry::<Cooldown>()
+            .and_modify(move |mut cooldown| cooldown.extend(amount));
+
+        true
+    }
+
+    /// Finishes the running cooldown of an ability or slot immediately. Returns `false` if it
+    /// isn't on cooldown.
+    pub fn reset_cooldown(&mut self, entity: Entity) -> bool {
+        if self.remaining_cooldown(entity).is_none() {
+            return false;
+        }
+
+        self.finish_cooldown(entity);
+        true
+    }
+
+    /// Reduces the running cooldowns of all abilities and slots of `character` by `amount`
+    pub fn reduce_cooldowns_of(&mut self, character: Entity, amount: Duration) {
+        let entities = self
+            .cooldowns_of(character)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+
+        for entity in entities {
+            self.reduce_cooldown(entity, amount);
+        }
+    }
+
+    /// Finishes the running cooldowns of all abilities and slots of `character` immediately
+    pub fn reset_cooldowns_of(&mut self, character: Entity) {
+        let entities = self
+            .cooldowns_of(character)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+
+        for entity in entities {
+            self.finish_cooldown(entity);
+        }
+    }
+
+    fn finish_cooldown(&mut self, entity: Entity) {
+        let character = self.find_character_of_cooldown(entity);
+
+        self.commands.entity(entity).remove::<Cooldown>();
+        self.commands
+            .trigger(CooldownFinished { entity, character });
+    }
+
+    /// Returns the duration for which the [`CooldownGroup`] of `ability_e` is still on cooldown
+    /// for `character`, `None` if the ability has no group or its group isn't on cooldown
+    pub fn remaining_group_cooldown(
+        &self,
+        character: Entity,
+        ability_e: Entity,
+    ) -> Option<Duration> {
+        let cooldown_group = self.cooldown_groups.get(ability_e).ok()?;
+
+        self.group_cooldowns
+            .get(character)
+            .ok()?
+            .remaining_cooldown(&cooldown_group.group)
+    }
+
+    /// Whether `character` can't use `ability_e` because its [`CooldownGroup`] is on cooldown
+    pub fn is_blocked_by_group_cooldown(&self, character: Entity, ability_e: Entity) -> bool {
+        self.remaining_group_cooldown(character, ability_e)
+            .is_some()
+    }
+
+    /// Starts the cooldown of the [`CooldownGroup`] of `ability_e` for `character`, if the
+    /// ability has one
+    pub fn start_group_cooldown(&mut self, character: Entity, ability_e: Entity) {
+        let Ok(AbilityCooldownGroup { group, duration }) =
+            self.cooldown_groups.get(ability_e).cloned()
+        else {
+            return;
+        };
+
+        self.commands
+            .entity(character)
+            .entry::<GroupCooldowns>()
+            .or_default()
+            .and_modify(move |mut group_cooldowns| group_cooldowns.start(group, duration));
+    }
+
+    /// Returns the running global cooldown of `character`, if any
+    pub fn active_global_cooldown(&self, character: Entity) -> Option<&ActiveGlobalCooldown> {
+        self.active_global_cooldowns.get(character).ok()
+    }
+
+    /// Whether `ability_e` is on the global cooldown
+    pub fn is_on_global_cooldown(&self, ability_e: Entity) -> bool {
+        self.on_global_cooldown.get(ability_e).unwrap_or(false)
+    }
+
+    /// Whether `character` can't use `ability_e` because of its running global cooldown
+    pub fn is_blocked_by_global_cooldown(&self, character: Entity, ability_e: Entity) -> bool {
+        self.is_on_global_cooldown(ability_e) && self.active_global_cooldowns.contains(character)
+    }
+
+    /// Whether using `ability_e` starts the global cooldown of `character`
+    pub fn starts_global_cooldown(&self, character: Entity, ability_e: Entity) -> bool {
+        self.is_on_global_cooldown(ability_e) && self.global_cooldowns.contains(character)
+    }
+
+    /// Starts the [`GlobalCooldown`] of `character`, if it has one
+    pub fn start_global_cooldown(&mut self, character: Entity) {
+        if let Ok(global_cooldown) = self.global_cooldowns.get(character) {
+            self.commands
+                .entity(character)
+                .insert(ActiveGlobalCooldown::new(global_cooldown.duration));
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -85,10 +700,28 @@ pub struct CooldownPlugin;
 
 impl Plugin for CooldownPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<Cooldown>().add_systems(
-            FixedUpdate,
-            tick_cooldowns.in_set(PerUpdateSet::LogicUpdate),
-        );
+        app.register_type::<Cooldown>()
+            .register_type::<AbilityCharges>()
+            .register_type::<GlobalCooldown>()
+            .register_type::<ActiveGlobalCooldown>()
+            .register_type::<GroupCooldowns>()
+            .register_type::<CooldownTiming>()
+            .register_type::<CooldownAwaitingEffectExpiry>()
+            .add_message::<DamageDealt>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    tick_cooldowns,
+                    tick_ability_charges,
+                    tick_global_cooldowns,
+                    tick_group_cooldowns,
+                )
+                    .in_set(PerUpdateSet::LogicUpdate),
+            )
+            .add_systems(
+                Update,
+                reset_cooldowns_on_kill.in_set(DamageResolutionStep::Post),
+            );
     }
 }
 
@@ -96,19 +729,34 @@ impl Plugin for CooldownPlugin {
 mod tests {
     use std::{assert_matches::assert_matches, time::Duration};
 
-    use bevy::{log::LogPlugin, prelude::*, time::TimeUpdateStrategy};
+    use bevy::{ecs::system::RunSystemOnce, log::LogPlugin, prelude::*, time::TimeUpdateStrategy};
 
-    use super::{Cooldown, CooldownPlugin};
+    use super::{
+        AbilityCharges, ActiveGlobalCooldown, Cooldown, CooldownAwaitingEffectExpiry,
+        CooldownFinished, CooldownInterface, CooldownPlugin, CooldownStart, CooldownTiming,
+        GlobalCooldown, InterruptedCooldown,
+    };
     use crate::{
+        abilities::weapon_attack,
         game_logic::{
-            ability::{Ability, AbilityCooldown, AbilityId},
-            ability_casting::AbilityCastingPlugin,
+            ability::{
+                Ability, AbilityCastTime, AbilityCooldown, AbilityCooldownGroup, AbilityId,
+                CooldownGroup, OnGlobalCooldown,
+            },
+            ability_casting::{
+                AbilityCastingInterface, AbilityCastingPlugin, InvalidCastReason, UseAbility,
+            },
             ability_slots::{AbilitySlot, AbilitySlotType},
+            ability_stats::StatModifierKind,
+            character_stats::{CharacterStat, CharacterStatModifier, CharacterStatModifiers},
             commands::CommandsPlugin,
+            crowd_control::StunEffect,
+            effects::{EffectApplied, EffectRemoved, EffectSource, EffectType},
             fight::{FightPlugin, FightTime},
             ongoing_cast::{OngoingCastFinishedSuccessfully, OngoingCastPlugin},
         },
         test_utils::{TestFightEntities, spawn_test_fight},
+        utils::holds_held::Held,
     };
 
     #[test]
@@ -281,4 +929,675 @@ mod tests {
             "Cooldown should be removed after finishing"
         );
     }
+
+    #[test]
+    fn test_cooldown_rate_modifies_cooldown_recovery() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(CooldownPlugin);
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            slot_e: _,
+            ability_e,
+            enemy_e: _,
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+
+        app.world_mut()
+            .entity_mut(caster_e)
+            .insert(CharacterStatModifiers(vec![CharacterStatModifier {
+                stat: CharacterStat::CooldownRate,
+                kind: StatModifierKind::Multiply(2.0),
+            }]));
+        app.world_mut()
+            .entity_mut(ability_e)
+            .insert(Cooldown::new(Duration::from_millis(100)));
+
+        // update once to initialize all systems etc., required when testing with manual time.
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            65,
+        )));
+
+        // 65ms at double rate finish the 100ms cooldown
+        app.update();
+
+        assert!(app.world().get::<Cooldown>(ability_e).is_none());
+    }
+
+    #[test]
+    fn test_ability_charges_are_spent_and_regained_one_at_a_time() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(CooldownPlugin)
+            .add_plugins(AbilityCastingPlugin)
+            .add_plugins(OngoingCastPlugin);
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            slot_e,
+            ability_e,
+            enemy_e,
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+        // only the charges should limit the uses of the ability
+        app.world_mut()
+            .entity_mut(ability_e)
+            .remove::<AbilityCooldown>()
+            .insert(AbilityCharges::new(2, Duration::from_millis(100)));
+
+        // finish the casts in another slot, so the slot of the test fight stays off cooldown
+        let other_slot_e = app
+            .world_mut()
+            .spawn(AbilitySlot {
+                tpe: AbilitySlotType::WeaponAttack,
+                on_use_cooldown: None,
+            })
+            .id();
+
+        let is_valid_cast = |app: &mut App| {
+            app.world_mut()
+                .run_system_once(move |casting_interface: AbilityCastingInterface| {
+                    casting_interface
+                        .is_valid_cast(&UseAbility {
+                            caster_e,
+                            slot_e,
+                            ability_e,
+                            target: Some(enemy_e),
+                            fight_e,
+                        })
+                        .is_ok()
+                })
+                .unwrap()
+        };
+        let current_charges = |app: &App| {
+            app.world()
+                .get::<AbilityCharges>(ability_e)
+                .unwrap()
+                .current_charges()
+        };
+
+        // update once to initialize all systems etc., required when testing with manual time.
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            65,
+        )));
+
+        for _ in 0..2 {
+            assert!(is_valid_cast(&mut app));
+            app.world_mut().trigger(OngoingCastFinishedSuccessfully {
+                slot_entity: other_slot_e,
+                ability_entity: ability_e,
+                caster_entity: Some(caster_e),
+                cast_target: Some(enemy_e),
+            });
+        }
+
+        assert_eq!(current_charges(&app), 0);
+        assert!(!is_valid_cast(&mut app));
+
+        // ~125ms: the first charge is back
+        app.update();
+        app.update();
+        assert_eq!(current_charges(&app), 1);
+        assert!(is_valid_cast(&mut app));
+
+        // ~187ms: the second charge is still recharging
+        app.update();
+        assert_eq!(current_charges(&app), 1);
+
+        // ~250ms: all charges are back, and stay that way
+        app.update();
+        assert_eq!(current_charges(&app), 2);
+        assert_eq!(
+            app.world()
+                .get::<AbilityCharges>(ability_e)
+                .unwrap()
+                .time_to_next_charge(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_global_cooldown_blocks_abilities_on_it() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(CooldownPlugin)
+            .add_plugins(AbilityCastingPlugin)
+            .add_plugins(OngoingCastPlugin);
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            ability_e,
+            enemy_e,
+            ..
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+        app.world_mut().entity_mut(caster_e).insert(GlobalCooldown {
+            duration: Duration::from_millis(100),
+        });
+
+        // two abilities on the global cooldown, each with its own slot without cooldown
+        let other_ability_e = weapon_attack::spawn_weapon_attack(&mut app.world_mut().commands());
+        app.world_mut().flush();
+        app.world_mut()
+            .entity_mut(caster_e)
+            .add_one_related::<Held<Ability>>(other_ability_e);
+
+        let casts = [ability_e, other_ability_e].map(|ability_e| {
+            app.world_mut()
+                .entity_mut(ability_e)
+                .insert(OnGlobalCooldown);
+            let slot_e = app
+                .world_mut()
+                .spawn(AbilitySlot {
+                    tpe: AbilitySlotType::WeaponAttack,
+                    on_use_cooldown: None,
+                })
+                .id();
+            app.world_mut()
+                .entity_mut(caster_e)
+                .add_one_related::<Held<AbilitySlot>>(slot_e);
+
+            UseAbility {
+                caster_e,
+                slot_e,
+                ability_e,
+                target: Some(enemy_e),
+                fight_e,
+            }
+        });
+
+        let validate = |app: &mut App, cast: UseAbility| {
+            app.world_mut()
+                .run_system_once(move |casting_interface: AbilityCastingInterface| {
+                    casting_interface.is_valid_cast(&cast)
+                })
+                .unwrap()
+        };
+
+        // update once to initialize all systems etc., required when testing with manual time.
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            65,
+        )));
+
+        // both are requested in the same frame, but only one of them is cast
+        for cast in casts.clone() {
+            app.world_mut().spawn(cast);
+        }
+        app.update();
+
+        assert!(app.world().get::<ActiveGlobalCooldown>(caster_e).is_some());
+        let not_cast = casts
+            .into_iter()
+            .filter(|cast| app.world().get::<Cooldown>(cast.ability_e).is_none())
+            .collect::<Vec<_>>();
+        assert_eq!(not_cast.len(), 1);
+        assert_matches!(
+            validate(&mut app, not_cast[0].clone()),
+            Err(InvalidCastReason::GlobalCooldown)
+        );
+
+        // abilities that are not on the global cooldown can still be used
+        app.world_mut()
+            .entity_mut(not_cast[0].ability_e)
+            .remove::<OnGlobalCooldown>();
+        assert_matches!(validate(&mut app, not_cast[0].clone()), Ok(()));
+        app.world_mut()
+            .entity_mut(not_cast[0].ability_e)
+            .insert(OnGlobalCooldown);
+
+        // ~130ms, the global cooldown finished
+        app.update();
+        assert!(app.world().get::<ActiveGlobalCooldown>(caster_e).is_none());
+        assert_matches!(validate(&mut app, not_cast[0].clone()), Ok(()));
+    }
+
+    #[test]
+    fn test_group_cooldown_is_shared_by_all_abilities_of_the_group() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(CooldownPlugin)
+            .add_plugins(AbilityCastingPlugin)
+            .add_plugins(OngoingCastPlugin);
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            ability_e,
+            enemy_e,
+            ..
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+
+        let other_ability_e = weapon_attack::spawn_weapon_attack(&mut app.world_mut().commands());
+        let unrelated_ability_e =
+            weapon_attack::spawn_weapon_attack(&mut app.world_mut().commands());
+        app.world_mut().flush();
+        app.world_mut()
+            .entity_mut(caster_e)
+            .add_related::<Held<Ability>>(&[other_ability_e, unrelated_ability_e]);
+
+        for grouped_ability_e in [ability_e, other_ability_e] {
+            app.world_mut()
+                .entity_mut(grouped_ability_e)
+                .insert(AbilityCooldownGroup {
+                    group: CooldownGroup("test".into()),
+                    duration: Duration::from_millis(100),
+                });
+        }
+
+        // a slot without cooldown, so only the ability and group cooldowns matter
+        let free_slot_e = app
+            .world_mut()
+            .spawn(AbilitySlot {
+                tpe: AbilitySlotType::WeaponAttack,
+                on_use_cooldown: None,
+            })
+            .id();
+        app.world_mut()
+            .entity_mut(caster_e)
+            .add_one_related::<Held<AbilitySlot>>(free_slot_e);
+
+        let validate = |app: &mut App, ability_e: Entity| {
+            app.world_mut()
+                .run_system_once(move |casting_interface: AbilityCastingInterface| {
+                    casting_interface.is_valid_cast(&UseAbility {
+                        caster_e,
+                        slot_e: free_slot_e,
+                        ability_e,
+                        target: Some(enemy_e),
+                        fight_e,
+                    })
+                })
+                .unwrap()
+        };
+
+        // update once to initialize all systems etc., required when testing with manual time.
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            65,
+        )));
+
+        app.world_mut().trigger(OngoingCastFinishedSuccessfully {
+            slot_entity: free_slot_e,
+            ability_entity: ability_e,
+            caster_entity: Some(caster_e),
+            cast_target: Some(enemy_e),
+        });
+        app.world_mut().flush();
+
+        assert_matches!(
+            validate(&mut app, other_ability_e),
+            Err(InvalidCastReason::GroupCooldown)
+        );
+        assert_matches!(validate(&mut app, unrelated_ability_e), Ok(()));
+
+        // ~125ms, the group cooldown finished, but the ability's own cooldown is still running
+        app.update();
+        app.update();
+        assert_matches!(validate(&mut app, other_ability_e), Ok(()));
+        assert_matches!(
+            validate(&mut app, ability_e),
+            Err(InvalidCastReason::AbilityOrSlotOnCooldown)
+        );
+    }
+
+    #[derive(Debug, Default, Resource)]
+    struct FinishedCooldowns(Vec<CooldownFinished>);
+
+    #[test]
+    fn test_cooldown_interface_modifies_running_cooldowns() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(CooldownPlugin)
+            .init_resource::<FinishedCooldowns>()
+            .add_observer(
+                |trigger: On<CooldownFinished>, mut finished: ResMut<FinishedCooldowns>| {
+                    finished.0.push(trigger.event().clone());
+                },
+            );
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            slot_e,
+            ability_e,
+            ..
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+
+        app.world_mut()
+            .entity_mut(ability_e)
+            .insert(Cooldown::new(Duration::from_secs(10)));
+        app.world_mut()
+            .entity_mut(slot_e)
+            .insert(Cooldown::new(Duration::from_secs(4)));
+
+        let remaining = |app: &mut App, entity: Entity| {
+            app.world_mut()
+                .run_system_once(move |cooldowns: CooldownInterface| {
+                    cooldowns.remaining_cooldown(entity)
+                })
+                .unwrap()
+        };
+        let modify = |app: &mut App, f: fn(&mut CooldownInterface, Entity, Entity) -> bool| {
+            app.world_mut()
+                .run_system_once(move |mut cooldowns: CooldownInterface| {
+                    f(&mut cooldowns, ability_e, slot_e)
+                })
+                .unwrap()
+        };
+
+        let mut cooldowns_of_caster = app
+            .world_mut()
+            .run_system_once(move |cooldowns: CooldownInterface| {
+                cooldowns.cooldowns_of(caster_e).collect::<Vec<_>>()
+            })
+            .unwrap();
+        cooldowns_of_caster.sort();
+        let mut expected = vec![
+            (ability_e, Duration::from_secs(10)),
+            (slot_e, Duration::from_secs(4)),
+        ];
+        expected.sort();
+        assert_eq!(cooldowns_of_caster, expected);
+
+        assert!(modify(&mut app, |cooldowns, ability_e, _| {
+            cooldowns.reduce_cooldown(ability_e, Duration::from_secs(3))
+        }));
+        assert_eq!(remaining(&mut app, ability_e), Some(Duration::from_secs(7)));
+
+        assert!(modify(&mut app, |cooldowns, ability_e, _| {
+            cooldowns.extend_cooldown(ability_e, Duration::from_secs(1))
+        }));
+        assert_eq!(remaining(&mut app, ability_e), Some(Duration::from_secs(8)));
+
+        assert!(modify(&mut app, |cooldowns, ability_e, _| {
+            cooldowns.reduce_cooldown_by_fraction(ability_e, 0.25)
+        }));
+        assert_eq!(remaining(&mut app, ability_e), Some(Duration::from_secs(6)));
+
+        // reducing by more than remains finishes the cooldown
+        assert!(modify(&mut app, |cooldowns, _, slot_e| {
+            cooldowns.reduce_cooldown(slot_e, Duration::from_secs(5))
+        }));
+        assert_eq!(remaining(&mut app, slot_e), None);
+        assert!(!modify(&mut app, |cooldowns, _, slot_e| {
+            cooldowns.reset_cooldown(slot_e)
+        }));
+
+        assert!(modify(&mut app, |cooldowns, ability_e, _| {
+            cooldowns.reset_cooldown(ability_e)
+        }));
+        assert_eq!(remaining(&mut app, ability_e), None);
+
+        let finished = &app.world().resource::<FinishedCooldowns>().0;
+        assert_eq!(
+            finished
+                .iter()
+                .map(|event| (event.entity, event.character))
+                .collect::<Vec<_>>(),
+            [(slot_e, Some(caster_e)), (ability_e, Some(caster_e))]
+        );
+
+        // cooldowns running out trigger it as well
+        app.world_mut()
+            .entity_mut(ability_e)
+            .insert(Cooldown::new(Duration::from_millis(50)));
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            65,
+        )));
+        app.update();
+
+        assert_eq!(remaining(&mut app, ability_e), None);
+        assert_eq!(app.world().resource::<FinishedCooldowns>().0.len(), 3);
+    }
+
+    #[test]
+    fn test_cooldown_timing_starts_cooldowns_at_the_configured_point() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(CooldownPlugin)
+            .add_plugins(AbilityCastingPlugin)
+            .add_plugins(OngoingCastPlugin);
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            slot_e,
+            ability_e,
+            enemy_e,
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+        app.world_mut().entity_mut(ability_e).insert((
+            AbilityCastTime(Duration::from_secs(1)),
+            CooldownTiming {
+                start: CooldownStart::CastStart,
+                ..default()
+            },
+        ));
+        app.world_mut().entity_mut(slot_e).insert(CooldownTiming {
+            on_interrupt: InterruptedCooldown::Partial(0.5),
+            ..default()
+        });
+
+        let remaining = |app: &mut App, entity: Entity| {
+            app.world_mut()
+                .run_system_once(move |cooldowns: CooldownInterface| {
+                    cooldowns.remaining_cooldown(entity)
+                })
+                .unwrap()
+        };
+
+        // update once to initialize all systems etc., required when testing with manual time.
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            65,
+        )));
+
+        app.world_mut().spawn(UseAbility {
+            caster_e,
+            slot_e,
+            ability_e,
+            target: Some(enemy_e),
+            fight_e,
+        });
+        app.update();
+
+        // the ability commits on cast start, the slot only when the cast finishes
+        assert!(remaining(&mut app, ability_e).is_some());
+        assert_eq!(remaining(&mut app, slot_e), None);
+
+        // interrupting starts the partial cooldown of the slot
+        app.world_mut()
+            .run_system_once(move |mut casting_interface: AbilityCastingInterface| {
+                assert!(casting_interface.interrupt_casts_of(caster_e, Some(enemy_e), None));
+            })
+            .unwrap();
+        assert_eq!(
+            remaining(&mut app, slot_e),
+            Some(Duration::from_millis(500))
+        );
+
+        // cooldowns starting on effect expiry wait for an effect of the ability to end
+        app.world_mut()
+            .entity_mut(ability_e)
+            .remove::<Cooldown>()
+            .insert(CooldownTiming {
+                start: CooldownStart::EffectExpiry,
+                ..default()
+            });
+        app.world_mut().trigger(OngoingCastFinishedSuccessfully {
+            slot_entity: slot_e,
+            ability_entity: ability_e,
+            caster_entity: Some(caster_e),
+            cast_target: Some(enemy_e),
+        });
+        app.world_mut().flush();
+
+        // the cooldown only waits once the ability applied an effect
+        assert!(remaining(&mut app, ability_e).is_some());
+        app.world_mut().trigger(EffectApplied {
+            target: enemy_e,
+            effect_entity: Entity::PLACEHOLDER,
+            effect_type: EffectType::of::<StunEffect>(),
+            source: EffectSource {
+                caster: Some(caster_e),
+                ability: Some(ability_e),
+            },
+        });
+        app.world_mut().flush();
+
+        assert_eq!(remaining(&mut app, ability_e), None);
+        assert_eq!(
+            app.world()
+                .get::<CooldownAwaitingEffectExpiry>(ability_e)
+                .map(|awaiting| awaiting.ability_e),
+            Some(ability_e)
+        );
+        assert!(
+            app.world_mut()
+                .run_system_once(move |cooldowns: CooldownInterface| {
+                    cooldowns.is_on_cooldown(ability_e)
+                })
+                .unwrap()
+        );
+
+        app.world_mut().trigger(EffectRemoved {
+            target: enemy_e,
+            effect_entity: Entity::PLACEHOLDER,
+            effect_type: EffectType::of::<StunEffect>(),
+            source: EffectSource {
+                caster: Some(caster_e),
+                ability: Some(ability_e),
+            },
+        });
+        app.world_mut().flush();
+
+        let cooldown_duration = app
+            .world()
+            .get::<AbilityCooldown>(ability_e)
+            .unwrap()
+            .duration;
+        assert_eq!(remaining(&mut app, ability_e), Some(cooldown_duration));
+        assert!(
+            app.world()
+                .get::<CooldownAwaitingEffectExpiry>(ability_e)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_effect_expiry_cooldown_starts_on_cast_finish_if_no_effect_is_applied() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(CooldownPlugin)
+            .add_plugins(AbilityCastingPlugin)
+            .add_plugins(OngoingCastPlugin);
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            slot_e,
+            ability_e,
+            enemy_e,
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+        app.world_mut().entity_mut(ability_e).insert((
+            AbilityCooldown {
+                duration: Duration::from_millis(100),
+            },
+            CooldownTiming {
+                start: CooldownStart::EffectExpiry,
+                ..default()
+            },
+        ));
+
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            65,
+        )));
+
+        // e.g., the target is immune, so the ability applies no effect
+        app.world_mut().trigger(OngoingCastFinishedSuccessfully {
+            slot_entity: slot_e,
+            ability_entity: ability_e,
+            caster_entity: Some(caster_e),
+            cast_target: Some(enemy_e),
+        });
+        app.world_mut().flush();
+
+        assert_eq!(
+            app.world()
+                .get::<Cooldown>(ability_e)
+                .map(Cooldown::remaining_cooldown),
+            Some(Duration::from_millis(100))
+        );
+        assert!(
+            app.world()
+                .get::<CooldownAwaitingEffectExpiry>(ability_e)
+                .is_none()
+        );
+
+        app.update();
+        app.update();
+
+        assert!(
+            !app.world_mut()
+                .run_system_once(move |cooldowns: CooldownInterface| {
+                    cooldowns.is_on_cooldown(ability_e)
+                })
+                .unwrap(),
+            "the ability must not stay locked"
+        );
+    }
 }
@@ -0,0 +1,291 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    game_logic::{
+        ability::CastFailureReason,
+        ability_casting::InvalidCastReason,
+        ability_slots::{AbilitySlot, AbilitySlotType},
+        effects::{
+            EffectCategory, EffectClassification, EffectInterface, GameEffect, ReflectGameEffect,
+            StackableEffect, StackingPolicy,
+        },
+        ongoing_cast::{CastAbortReason, OngoingCastInterface},
+    },
+    utils::holds_held::Holds,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum CrowdControl {
+    /// Prevents all casts, and interrupts ongoing casts when applied.
+    Stun,
+    /// Prevents casts in [`AbilitySlotType::Magic`] slots.
+    Silence,
+    /// Prevents casts in [`AbilitySlotType::WeaponAttack`] slots.
+    Disarm,
+}
+
+impl CrowdControl {
+    /// Whether this prevents casting abilities in a slot of `slot_type`.
+    pub fn blocks(&self, slot_type: AbilitySlotType) -> bool {
+        match self {
+            CrowdControl::Stun => true,
+            CrowdControl::Silence => slot_type == AbilitySlotType::Magic,
+            CrowdControl::Disarm => slot_type == AbilitySlotType::WeaponAttack,
+        }
+    }
+
+    pub fn cast_failure_reason(&self) -> CastFailureReason {
+        match self {
+            CrowdControl::Stun => CastFailureReason::Stunned,
+            CrowdControl::Silence => CastFailureReason::Silenced,
+            CrowdControl::Disarm => CastFailureReason::Disarmed,
+        }
+    }
+
+    pub fn invalid_cast_reason(&self) -> InvalidCastReason {
+        match self {
+            CrowdControl::Stun => InvalidCastReason::Stunned,
+            CrowdControl::Silence => InvalidCastReason::Silenced,
+            CrowdControl::Disarm => InvalidCastReason::Disarmed,
+        }
+    }
+}
+
+impl std::fmt::Display for CrowdControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrowdControl::Stun => write!(f, "Stunned"),
+            CrowdControl::Silence => write!(f, "Silenced"),
+            CrowdControl::Disarm => write!(f, "Disarmed"),
+        }
+    }
+}
+
+/// An effect that applies a [`CrowdControl`] to its holder until its [`EffectTimer`] finishes.
+///
+/// [`EffectTimer`]: crate::game_logic::effects::EffectTimer
+pub trait CrowdControlEffect: StackableEffect + Default {
+    const CROWD_CONTROL: CrowdControl;
+}
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(GameEffect)]
+pub struct StunEffect;
+
+impl GameEffect for StunEffect {}
+
+impl StackableEffect for StunEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::RefreshDuration;
+    const CLASSIFICATION: EffectClassification =
+        EffectClassification::debuff(EffectCategory::Physical)
+            .undispellable()
+            .with_diminishing_returns();
+}
+
+impl CrowdControlEffect for StunEffect {
+    const CROWD_CONTROL: CrowdControl = CrowdControl::Stun;
+}
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(GameEffect)]
+pub struct SilenceEffect;
+
+impl GameEffect for SilenceEffect {}
+
+impl StackableEffect for SilenceEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::RefreshDuration;
+    const CLASSIFICATION: EffectClassification =
+        EffectClassification::debuff(EffectCategory::Magic).with_diminishing_returns();
+}
+
+impl CrowdControlEffect for SilenceEffect {
+    const CROWD_CONTROL: CrowdControl = CrowdControl::Silence;
+}
+
+#[derive(Debug, Default, Component, Reflect)]
+#[reflect(GameEffect)]
+pub struct DisarmEffect;
+
+impl GameEffect for DisarmEffect {}
+
+impl StackableEffect for DisarmEffect {
+    const STACKING_POLICY: StackingPolicy = StackingPolicy::RefreshDuration;
+    const CLASSIFICATION: EffectClassification =
+        EffectClassification::debuff(EffectCategory::Physical).with_diminishing_returns();
+}
+
+impl CrowdControlEffect for DisarmEffect {
+    const CROWD_CONTROL: CrowdControl = CrowdControl::Disarm;
+}
+
+#[derive(SystemParam)]
+pub struct CrowdControlInterface<'w, 's> {
+    stuns: EffectInterface<'w, 's, StunEffect>,
+    silences: EffectInterface<'w, 's, SilenceEffect>,
+    disarms: EffectInterface<'w, 's, DisarmEffect>,
+}
+
+impl<'w, 's> CrowdControlInterface<'w, 's> {
+    /// Returns all crowd control currently applied to `character`.
+    pub fn active_crowd_control(&self, character: Entity) -> Vec<CrowdControl> {
+        [
+            self.stuns.get_effect(character).map(|_| CrowdControl::Stun),
+            self.silences
+                .get_effect(character)
+                .map(|_| CrowdControl::Silence),
+            self.disarms
+                .get_effect(character)
+                .map(|_| CrowdControl::Disarm),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Returns the crowd control that prevents `character` from casting in a slot of
+    /// `slot_type`, if any.
+    pub fn blocking_crowd_control(
+        &self,
+        character: Entity,
+        slot_type: AbilitySlotType,
+    ) -> Option<CrowdControl> {
+        self.active_crowd_control(character)
+            .into_iter()
+            .find(|cc| cc.blocks(slot_type))
+    }
+}
+
+/// Observer that interrupts all ongoing casts of a character when it gets stunned
+fn interrupt_casts_on_stun(
+    trigger: On<Add, StunEffect>,
+    stuns: EffectInterface<StunEffect>,
+    holds_slots: Query<&Holds<AbilitySlot>>,
+    mut ongoing_cast_interface: OngoingCastInterface,
+) {
+    let stunned_e = stuns.get_target_of_effect(trigger.entity);
+
+    for slot_e in holds_slots.relationship_sources(stunned_e) {
+        if ongoing_cast_interface.get_ongoing_cast(slot_e).is_some() {
+            ongoing_cast_interface
+                .cancel_ongoing_cast(slot_e, CastAbortReason::Interrupted { by: None });
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CrowdControlPlugin;
+
+impl Plugin for CrowdControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<StunEffect>()
+            .register_type::<SilenceEffect>()
+            .register_type::<DisarmEffect>()
+            .add_observer(interrupt_casts_on_stun);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{assert_matches::assert_matches, time::Duration};
+
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+    use super::{CrowdControlEffect, CrowdControlPlugin, DisarmEffect, SilenceEffect, StunEffect};
+    use crate::{
+        game_logic::{
+            ability_casting::{AbilityCastingInterface, InvalidCastReason, UseAbility},
+            commands::CommandsPlugin,
+            effects::{EffectInterface, EffectSource, EffectTimer, EffectsPlugin},
+            fight::FightPlugin,
+            ongoing_cast::{OngoingCast, OngoingCastPlugin},
+        },
+        test_utils::{TestFightEntities, spawn_test_fight},
+    };
+
+    fn apply<E: CrowdControlEffect>(app: &mut App, target: Entity) {
+        app.world_mut()
+            .run_system_once(move |mut effects_interface: EffectInterface<E>| {
+                effects_interface.apply_timed_effect(
+                    target,
+                    EffectSource::default(),
+                    E::default(),
+                    EffectTimer::duration(Duration::from_secs(2)),
+                );
+            })
+            .unwrap();
+    }
+
+    fn validate(app: &mut App, cast: UseAbility) -> Result<(), InvalidCastReason> {
+        app.world_mut()
+            .run_system_once(move |interface: AbilityCastingInterface| {
+                interface.is_valid_cast(&cast)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_crowd_control_blocks_casts_by_slot_type() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .add_plugins(CrowdControlPlugin);
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            slot_e,
+            ability_e,
+            enemy_e,
+        } = spawn_test_fight(&mut app);
+        let weapon_attack = UseAbility {
+            caster_e,
+            slot_e,
+            ability_e,
+            target: Some(enemy_e),
+            fight_e,
+        };
+
+        // silence doesn't affect the weapon slot
+        apply::<SilenceEffect>(&mut app, caster_e);
+        assert_matches!(validate(&mut app, weapon_attack.clone()), Ok(()));
+
+        apply::<DisarmEffect>(&mut app, caster_e);
+        assert_matches!(
+            validate(&mut app, weapon_attack),
+            Err(InvalidCastReason::Disarmed)
+        );
+    }
+
+    #[test]
+    fn test_stun_interrupts_ongoing_casts() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .add_plugins(OngoingCastPlugin)
+            .add_plugins(CrowdControlPlugin);
+
+        let TestFightEntities {
+            fight_e: _,
+            caster_e,
+            slot_e,
+            ability_e,
+            enemy_e,
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut().entity_mut(slot_e).insert(OngoingCast {
+            ability_e,
+            caster_e: Some(caster_e),
+            target: Some(enemy_e),
+            cast_timer: Timer::new(Duration::from_secs(2), TimerMode::Once),
+        });
+
+        apply::<StunEffect>(&mut app, caster_e);
+        app.update();
+
+        assert!(app.world().get::<OngoingCast>(slot_e).is_none());
+    }
+}
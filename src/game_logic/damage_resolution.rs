@@ -1,29 +1,199 @@
+use std::collections::BTreeMap;
+
 use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::health::{Health, HealthInterface};
+use crate::{
+    PerUpdateSet,
+    game_logic::character_stats::{CharacterStat, CharacterStatsInterface},
+};
 
-use super::health::HealthInterface;
-use crate::{PerUpdateSet, game_logic::health::AlreadyDeadError};
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect, Deserialize,
+)]
+pub enum DamageType {
+    #[default]
+    Physical,
+    Magical,
+}
+
+impl std::fmt::Display for DamageType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DamageType::Physical => write!(f, "Physical"),
+            DamageType::Magical => write!(f, "Magical"),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Component, Reflect, PartialEq)]
 pub struct DamageInstance {
+    /// The character that caused the damage, if any.
     pub source: Option<Entity>,
+    /// The ability that caused the damage, directly or through an effect it applied.
+    pub source_ability: Option<Entity>,
     pub target: Entity,
     pub amount: f64,
+    pub damage_type: DamageType,
+}
+
+/// Reduces incoming damage of a character per [`DamageType`], e.g., `0.25` reduces damage by 25%.
+/// Physical resistance is the character's armor.
+#[derive(Debug, Clone, Default, Component, Reflect)]
+pub struct Resistances(BTreeMap<DamageType, f64>);
+
+impl Resistances {
+    pub fn new(resistances: impl IntoIterator<Item = (DamageType, f64)>) -> Self {
+        Self(resistances.into_iter().collect())
+    }
+
+    /// Returns the resistance against `damage_type`, clamped to `0.0..=1.0`.
+    pub fn get(&self, damage_type: DamageType) -> f64 {
+        self.0
+            .get(&damage_type)
+            .copied()
+            .unwrap_or_default()
+            .clamp(0.0, 1.0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (DamageType, f64)> + '_ {
+        self.0
+            .keys()
+            .map(|damage_type| (*damage_type, self.get(*damage_type)))
+    }
 }
 
 #[derive(Event, Message, Debug, Clone)]
 pub struct DealDamage(pub DamageInstance);
 
-fn damage_resolution_system(
-    mut deal_damage_events: MessageReader<DealDamage>,
+/// The amount of a [`DamageInstance`] that was absorbed so far while it is resolved.
+#[derive(Debug, Clone, Default, Component, Reflect)]
+pub struct AbsorbedDamage(pub f64);
+
+impl AbsorbedDamage {
+    /// Absorbs up to `max_amount` of `damage`, returns the absorbed amount.
+    pub fn absorb(&mut self, damage: &mut DamageInstance, max_amount: f64) -> f64 {
+        let absorbed = damage.amount.min(max_amount).max(0.0);
+
+        damage.amount -= absorbed;
+        self.0 += absorbed;
+
+        absorbed
+    }
+}
+
+/// The result of a fully resolved [`DealDamage`]. Not sent for targets that were already dead.
+#[derive(Message, Debug, Clone)]
+pub struct DamageDealt {
+    /// The instance after all modifiers and absorbs.
+    pub instance: DamageInstance,
+    /// The amount of health that was actually lost.
+    pub applied: f64,
+    pub absorbed: f64,
+    /// The amount exceeding the target's remaining health.
+    pub overkill: f64,
+}
+
+/// The steps of damage resolution, run in order within [`PerUpdateSet::DamageResolution`].
+///
+/// Every [`DealDamage`] is spawned as an entity with a [`DamageInstance`] and [`AbsorbedDamage`]
+/// in `Collect`. Handlers of abilities and effects are added to the other steps and can modify
+/// these entities; handlers within the same step can be ordered with `.before()`/`.after()`.
+/// `Apply` applies the damage to the targets' health and sends [`DamageDealt`], which can be
+/// reacted to in `Post`.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum DamageResolutionStep {
+    Collect,
+    /// Modifiers of the damage source, e.g., damage buffs.
+    Outgoing,
+    /// Modifiers of the damage target, e.g., damage reductions.
+    Incoming,
+    Absorb,
+    Apply,
+    Post,
+}
+
+fn collect_damage(mut deal_damage_events: MessageReader<DealDamage>, mut commands: Commands) {
+    for deal_damage_event in deal_damage_events.read() {
+        commands.spawn((deal_damage_event.0.clone(), AbsorbedDamage::default()));
+    }
+}
+
+/// Applies [`CharacterStat::OutgoingDamage`] of the source of each damage instance.
+fn apply_outgoing_damage_modifiers(
+    mut damage_instances: Query<&mut DamageInstance>,
+    character_stats: CharacterStatsInterface,
+) {
+    for mut damage in &mut damage_instances {
+        if let Some(source) = damage.source {
+            damage.amount =
+                character_stats.modified(source, CharacterStat::OutgoingDamage, damage.amount);
+        }
+    }
+}
+
+/// Applies [`CharacterStat::IncomingDamage`] of the target of each damage instance.
+fn apply_incoming_damage_modifiers(
+    mut damage_instances: Query<&mut DamageInstance>,
+    character_stats: CharacterStatsInterface,
+) {
+    for mut damage in &mut damage_instances {
+        damage.amount =
+            character_stats.modified(damage.target, CharacterStat::IncomingDamage, damage.amount);
+    }
+}
+
+fn apply_resistances(
+    mut damage_instances: Query<&mut DamageInstance>,
+    resistances: Query<&Resistances>,
+) {
+    for mut damage in &mut damage_instances {
+        if let Ok(resistances) = resistances.get(damage.target) {
+            damage.amount *= 1.0 - resistances.get(damage.damage_type);
+        }
+    }
+}
+
+fn apply_damage(
+    damage_instances: Query<(Entity, &DamageInstance, &AbsorbedDamage)>,
     mut health_interface: HealthInterface,
+    mut damage_dealt_events: MessageWriter<DamageDealt>,
+    mut commands: Commands,
 ) {
-    for deal_damage_event in deal_damage_events.read() {
-        let damage = &deal_damage_event.0;
+    for (damage_e, damage, absorbed) in damage_instances.iter() {
+        commands.entity(damage_e).despawn();
+
+        // the target might have been despawned since the damage was dealt
+        let Ok(remaining_hp) = health_interface
+            .healths()
+            .get(damage.target)
+            .map(Health::current)
+        else {
+            continue;
+        };
 
-        let res = health_interface.lose_hp(damage.target, damage.amount);
-        match res {
-            Ok(()) | Err(AlreadyDeadError) => (),
+        if health_interface
+            .lose_hp(damage.target, damage.amount)
+            .is_err()
+        {
+            continue;
         }
+
+        let applied = damage.amount.min(remaining_hp);
+
+        damage_dealt_events.write(DamageDealt {
+            instance: damage.clone(),
+            applied,
+            absorbed: absorbed.0,
+            overkill: damage.amount - applied,
+        });
+    }
+}
+
+fn log_damage_dealt(mut damage_dealt_events: MessageReader<DamageDealt>) {
+    for damage_dealt in damage_dealt_events.read() {
+        debug!("{damage_dealt:?}");
     }
 }
 
@@ -32,10 +202,124 @@ pub struct DamageResolutionPlugin;
 impl Plugin for DamageResolutionPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<DamageInstance>()
+            .register_type::<AbsorbedDamage>()
+            .register_type::<Resistances>()
             .add_message::<DealDamage>()
+            .add_message::<DamageDealt>()
+            .configure_sets(
+                Update,
+                (
+                    DamageResolutionStep::Collect,
+                    DamageResolutionStep::Outgoing,
+                    DamageResolutionStep::Incoming,
+                    DamageResolutionStep::Absorb,
+                    DamageResolutionStep::Apply,
+                    DamageResolutionStep::Post,
+                )
+                    .chain()
+                    .in_set(PerUpdateSet::DamageResolution),
+            )
             .add_systems(
                 Update,
-                damage_resolution_system.in_set(PerUpdateSet::DamageResolution),
+                (
+                    collect_damage.in_set(DamageResolutionStep::Collect),
+                    apply_outgoing_damage_modifiers.in_set(DamageResolutionStep::Outgoing),
+                    apply_incoming_damage_modifiers
+                        .in_set(DamageResolutionStep::Incoming)
+                        .before(apply_resistances),
+                    apply_resistances.in_set(DamageResolutionStep::Incoming),
+                    apply_damage.in_set(DamageResolutionStep::Apply),
+                    log_damage_dealt.in_set(DamageResolutionStep::Post),
+                ),
             );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::{
+        AbsorbedDamage, DamageDealt, DamageInstance, DamageResolutionPlugin, DamageResolutionStep,
+        DamageType, DealDamage, Resistances,
+    };
+    use crate::game_logic::health::{Health, HealthInterfacePlugin};
+
+    fn absorb_5(mut damage_instances: Query<(&mut DamageInstance, &mut AbsorbedDamage)>) {
+        for (mut damage, mut absorbed) in &mut damage_instances {
+            absorbed.absorb(&mut damage, 5.0);
+        }
+    }
+
+    fn double_damage(mut damage_instances: Query<&mut DamageInstance>) {
+        for mut damage in &mut damage_instances {
+            damage.amount *= 2.0;
+        }
+    }
+
+    #[test]
+    fn test_damage_dealt_after_all_steps() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(HealthInterfacePlugin)
+            .add_plugins(DamageResolutionPlugin)
+            .add_systems(
+                Update,
+                (
+                    // registered in reverse, the steps still order them
+                    absorb_5.in_set(DamageResolutionStep::Absorb),
+                    double_damage.in_set(DamageResolutionStep::Outgoing),
+                ),
+            );
+
+        let target = app.world_mut().spawn(Health::new(30.0)).id();
+
+        app.world_mut().write_message(DealDamage(DamageInstance {
+            source: None,
+            source_ability: None,
+            target,
+            amount: 20.0,
+            damage_type: DamageType::Physical,
+        }));
+        app.update();
+
+        // (20 * 2) - 5 = 35 against 30 health
+        assert_eq!(app.world().get::<Health>(target).unwrap().current(), -5.0);
+
+        let messages = app.world().resource::<Messages<DamageDealt>>();
+        let damage_dealt = messages.iter_current_update_messages().next().unwrap();
+        assert_eq!(damage_dealt.instance.amount, 35.0);
+        assert_eq!(damage_dealt.applied, 30.0);
+        assert_eq!(damage_dealt.absorbed, 5.0);
+        assert_eq!(damage_dealt.overkill, 5.0);
+    }
+
+    #[test]
+    fn test_resistances_reduce_damage_per_type() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(HealthInterfacePlugin)
+            .add_plugins(DamageResolutionPlugin);
+
+        let target = app
+            .world_mut()
+            .spawn((
+                Health::new(100.0),
+                Resistances::new([(DamageType::Physical, 0.5)]),
+            ))
+            .id();
+
+        for damage_type in [DamageType::Physical, DamageType::Magical] {
+            app.world_mut().write_message(DealDamage(DamageInstance {
+                source: None,
+                source_ability: None,
+                target,
+                amount: 20.0,
+                damage_type,
+            }));
+        }
+        app.update();
+
+        assert_eq!(app.world().get::<Health>(target).unwrap().current(), 70.0);
+    }
+}
@@ -1,115 +1,746 @@
-use bevy::{ecs::system::SystemParam, prelude::*};
-use itertools::Itertools;
+use std::time::Duration;
 
+use bevy::{
+    ecs::{component::Mutable, system::SystemParam},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    PerUpdateSet,
+    game_logic::{ability::PerformAbility, ability_stats::AbilityStats, fight::FightInterface},
+    utils::FiniteRepeatingTimer,
+};
+
+pub mod diminishing_returns;
+
+use diminishing_returns::{DiminishingReturnsInterface, DiminishingReturnsPlugin};
+
+/// Relates an effect entity to the character it affects.
 #[derive(Debug, Component, Reflect)]
-pub struct HasEffects {
-    // don't make this pub because there is no `OnModify`-Trigger (yet)
-    holder: Entity,
+#[relationship(relationship_target = HasEffects)]
+pub struct EffectOf(pub Entity);
+
+impl EffectOf {
+    #[inline(always)]
+    pub fn target(&self) -> Entity {
+        self.0
+    }
 }
 
+/// The effect entities of a character, in the order they were applied. Despawning the character
+/// despawns its effects.
+#[derive(Debug, Component, Reflect)]
+#[relationship_target(relationship = EffectOf, linked_spawn)]
+pub struct HasEffects(Vec<Entity>);
+
 impl HasEffects {
-    pub fn new(holder: Entity) -> Self {
-        Self { holder }
+    #[inline(always)]
+    pub fn effects(&self) -> &[Entity] {
+        &self.0
     }
+}
 
-    #[inline(always)]
-    pub fn holder(&self) -> Entity {
-        self.holder
+/// Marker trait for components that represent effects
+#[reflect_trait]
+pub trait GameEffect: Reflect + std::fmt::Debug {}
+
+/// How applying an effect behaves if the target already has an instance of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum StackingPolicy {
+    /// The existing instance is replaced by the new one.
+    Replace,
+    /// The existing instance's [`EffectTimer`] is reset to the new one.
+    RefreshDuration,
+    /// The new [`EffectTimer`] is added to the existing instance's, see [`EffectTimer::extend`].
+    ExtendDuration,
+    /// The existing instance gains a stack (up to `max_stacks`), and its duration is refreshed.
+    /// The stacks are tracked in [`EffectStacks`].
+    AddStack { max_stacks: u32 },
+    /// Each caster has its own instance, which is replaced when the same caster applies it again.
+    IndependentPerSource,
+}
+
+/// An effect that can be applied with [`EffectInterface`].
+pub trait StackableEffect: GameEffect + Component<Mutability = Mutable> {
+    const STACKING_POLICY: StackingPolicy;
+    const CLASSIFICATION: EffectClassification;
+    /// Makes the effect a delayed detonation, see [`DelayedDetonation`].
+    const DETONATION: Option<DelayedDetonation> = None;
+}
+
+/// An effect that abilities create from their stats, see
+/// [`crate::abilities::EffectApplyingAbility`].
+pub trait EffectFromStats: StackableEffect + Sized {
+    /// Creates the effect and its timer from the (resolved) stats of the ability applying it.
+    /// Returns `None` if a required stat is missing.
+    fn from_stats(stats: &AbilityStats) -> Option<(Self, EffectTimer)>;
+}
+
+/// Whether an effect is beneficial or harmful for its holder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Deserialize)]
+pub enum EffectDisposition {
+    Buff,
+    Debuff,
+}
+
+impl std::fmt::Display for EffectDisposition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EffectDisposition::Buff => write!(f, "Buff"),
+            EffectDisposition::Debuff => write!(f, "Debuff"),
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Deserialize)]
+pub enum EffectCategory {
+    Magic,
+    Physical,
+    Curse,
+}
+
+impl std::fmt::Display for EffectCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EffectCategory::Magic => write!(f, "Magic"),
+            EffectCategory::Physical => write!(f, "Physical"),
+            EffectCategory::Curse => write!(f, "Curse"),
+        }
+    }
+}
+
+/// The [`StackableEffect::CLASSIFICATION`] of an effect, inserted on each effect entity so
+/// effects can be dispelled without knowing their type statically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Reflect)]
+pub struct EffectClassification {
+    pub disposition: EffectDisposition,
+    pub category: EffectCategory,
+    /// Whether the effect can be removed by [`AnyEffectInterface::dispel()`].
+    pub dispellable: bool,
+    /// Whether repeated applications of this debuff lose effectiveness, and the target becomes
+    /// briefly immune to its category once it expires, see [`diminishing_returns`].
+    pub diminishing_returns: bool,
+}
+
+impl EffectClassification {
+    /// A dispellable buff.
+    pub const fn buff(category: EffectCategory) -> Self {
+        Self {
+            disposition: EffectDisposition::Buff,
+            category,
+            dispellable: true,
+            diminishing_returns: false,
+        }
+    }
+
+    /// A dispellable debuff.
+    pub const fn debuff(category: EffectCategory) -> Self {
+        Self {
+            disposition: EffectDisposition::Debuff,
+            category,
+            dispellable: true,
+            diminishing_returns: false,
+        }
+    }
+
+    pub const fn undispellable(self) -> Self {
+        Self {
+            dispellable: false,
+            ..self
+        }
+    }
+
+    /// Subjects a debuff to diminishing returns, see [`diminishing_returns`].
+    pub const fn with_diminishing_returns(self) -> Self {
+        Self {
+            diminishing_returns: true,
+            ..self
+        }
+    }
+}
+
+/// An effect that does nothing until it detonates: [`EffectDetonated`] is triggered when its
+/// [`EffectTimer`] expires, or early with [`AnyEffectInterface::detonate()`], e.g. when a
+/// condition of the effect is met. The effect implements its payload (damage, heals, applying
+/// other effects, ...) by observing [`EffectDetonated`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Reflect)]
+pub struct DelayedDetonation {
+    /// Whether the effect detonates when it is dispelled, instead of being defused.
+    pub detonate_on_dispel: bool,
+}
+
+/// Why a [`DelayedDetonation`] effect detonated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum DetonationReason {
+    /// Its [`EffectTimer`] finished.
+    Expired,
+    /// It was dispelled, and has [`DelayedDetonation::detonate_on_dispel`].
+    Dispelled,
+    /// It was detonated early with [`AnyEffectInterface::detonate()`].
+    Triggered,
+}
+
+/// Drives the lifetime of an effect: it is ticked by [`tick_effect_timers`], which triggers
+/// [`EffectTicked`] and [`EffectExpired`] and removes the effect once the timer is finished.
+/// Effects without an `EffectTimer` last until they are removed.
 #[derive(Debug, Component, Reflect)]
-pub struct EffectsHolder {
-    // don't make this pub because there is no `OnModify`-Trigger (yet)
-    holding_entity: Entity,
+pub enum EffectTimer {
+    /// Expires after the duration.
+    Duration(Timer),
+    /// Ticks once per interval, and expires with the last tick.
+    Periodic(FiniteRepeatingTimer),
 }
 
-impl EffectsHolder {
-    pub fn new(holding_entity: Entity) -> Self {
-        Self { holding_entity }
+impl EffectTimer {
+    pub fn duration(duration: Duration) -> Self {
+        Self::Duration(Timer::new(duration, TimerMode::Once))
     }
 
-    #[inline(always)]
-    pub fn holding_entity(&self) -> Entity {
-        self.holding_entity
+    pub fn periodic(tick_interval: Duration, num_ticks: u32) -> Self {
+        Self::Periodic(FiniteRepeatingTimer::new(tick_interval, num_ticks))
+    }
+
+    pub fn remaining(&self) -> Duration {
+        match self {
+            EffectTimer::Duration(timer) => timer.remaining(),
+            EffectTimer::Periodic(timer) => timer.remaining_time(),
+        }
+    }
+
+    /// The remaining ticks of a [`EffectTimer::Periodic`] timer.
+    pub fn remaining_ticks(&self) -> Option<u32> {
+        match self {
+            EffectTimer::Duration(_) => None,
+            EffectTimer::Periodic(timer) => Some(timer.remaining_ticks()),
+        }
+    }
+
+    /// The tick interval of a [`EffectTimer::Periodic`] timer.
+    pub fn tick_interval(&self) -> Option<Duration> {
+        match self {
+            EffectTimer::Duration(_) => None,
+            EffectTimer::Periodic(timer) => Some(timer.tick_interval()),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        match self {
+            EffectTimer::Duration(timer) => timer.is_finished(),
+            EffectTimer::Periodic(timer) => timer.is_finished(),
+        }
+    }
+
+    /// Adds the duration (or ticks) of `other` to this timer. Timers of different kinds can't be
+    /// combined, in this case `other` replaces this timer.
+    pub fn extend(&mut self, other: EffectTimer) {
+        match (self, other) {
+            (EffectTimer::Duration(timer), EffectTimer::Duration(other)) => {
+                let extended = timer.duration() + other.remaining();
+                timer.set_duration(extended);
+            }
+            (EffectTimer::Periodic(timer), EffectTimer::Periodic(other)) => {
+                timer.add_ticks(other.remaining_ticks());
+            }
+            (this, other) => *this = other,
+        }
+    }
+
+    /// Returns a new timer with its remaining duration (or ticks, rounded up) multiplied by
+    /// `factor`.
+    pub fn scaled(&self, factor: f64) -> Self {
+        match self {
+            EffectTimer::Duration(timer) => {
+                EffectTimer::duration(timer.remaining().mul_f64(factor))
+            }
+            EffectTimer::Periodic(timer) => EffectTimer::periodic(
+                timer.tick_interval(),
+                (f64::from(timer.remaining_ticks()) * factor).ceil() as u32,
+            ),
+        }
+    }
+
+    /// Returns the number of ticks that elapsed (always 0 for [`EffectTimer::Duration`]).
+    #[must_use]
+    fn tick(&mut self, delta: Duration) -> u32 {
+        match self {
+            EffectTimer::Duration(timer) => {
+                timer.tick(delta);
+                0
+            }
+            EffectTimer::Periodic(timer) => timer.tick_get_fresh_ticks(delta),
+        }
     }
 }
 
-/// Marker trait for components that represent effects
-#[reflect_trait]
-pub trait GameEffect: Reflect + std::fmt::Debug {}
+/// Triggered on the affected character when an [`EffectTimer::Periodic`] effect ticks. Effects
+/// implement their periodic behavior (e.g. damage over time) by observing this.
+#[derive(Debug, Clone, EntityEvent)]
+pub struct EffectTicked {
+    /// The character that holds the effect.
+    #[event_target]
+    pub target: Entity,
+    pub effect_entity: Entity,
+    /// The number of ticks that elapsed since the last update, usually 1.
+    pub ticks: u32,
+}
+
+/// Triggered on the affected character when a [`DelayedDetonation`] effect detonates, right
+/// before the effect is despawned.
+#[derive(Debug, Clone, EntityEvent)]
+pub struct EffectDetonated {
+    /// The character that holds the effect.
+    #[event_target]
+    pub target: Entity,
+    pub effect_entity: Entity,
+    pub source: EffectSource,
+    pub reason: DetonationReason,
+}
+
+/// Triggered on the affected character when an effect is applied to it, and it didn't have an
+/// instance of the effect that could be stacked/refreshed before.
+#[derive(Debug, Clone, EntityEvent)]
+pub struct EffectApplied {
+    #[event_target]
+    pub target: Entity,
+    pub effect_entity: Entity,
+    pub effect_type: EffectType,
+    pub source: EffectSource,
+}
+
+/// Triggered on the affected character when an effect is applied again to an existing instance,
+/// which is then replaced, refreshed, extended or stacked, according to its [`StackingPolicy`].
+#[derive(Debug, Clone, EntityEvent)]
+pub struct EffectRefreshed {
+    #[event_target]
+    pub target: Entity,
+    pub effect_entity: Entity,
+    pub effect_type: EffectType,
+    pub source: EffectSource,
+}
+
+/// Triggered on the affected character when the [`EffectTimer`] of an effect finishes, right
+/// before the effect is despawned.
+#[derive(Debug, Clone, EntityEvent)]
+pub struct EffectExpired {
+    #[event_target]
+    pub target: Entity,
+    pub effect_entity: Entity,
+    pub effect_type: EffectType,
+    pub source: EffectSource,
+}
+
+/// Triggered on the affected character when an effect is removed before it expired (e.g.
+/// consumed or dispelled), right before the effect is despawned.
+#[derive(Debug, Clone, EntityEvent)]
+pub struct EffectRemoved {
+    #[event_target]
+    pub target: Entity,
+    pub effect_entity: Entity,
+    pub effect_type: EffectType,
+    pub source: EffectSource,
+}
+
+/// Who and what applied the effect, so damage and heals produced by it can be attributed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Component, Reflect)]
+pub struct EffectSource {
+    /// The character that applied the effect, if any.
+    pub caster: Option<Entity>,
+    /// The ability that applied the effect, if any.
+    pub ability: Option<Entity>,
+}
+
+impl From<&PerformAbility> for EffectSource {
+    fn from(event: &PerformAbility) -> Self {
+        Self {
+            caster: event.caster,
+            ability: Some(event.ability_entity),
+        }
+    }
+}
+
+/// The type of the effect component on an effect entity, so effects can be identified without
+/// knowing their type statically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Reflect)]
+pub struct EffectType(&'static str);
+
+impl EffectType {
+    pub fn of<E: GameEffect>() -> Self {
+        Self(std::any::type_name::<E>())
+    }
+
+    pub fn is<E: GameEffect>(&self) -> bool {
+        *self == Self::of::<E>()
+    }
+
+    /// The name of the effect type, without its module path.
+    pub fn name(&self) -> &'static str {
+        self.0.rsplit_once("::").map_or(self.0, |(_, name)| name)
+    }
+}
+
+/// The number of stacks of an effect with [`StackingPolicy::AddStack`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Reflect)]
+pub struct EffectStacks(u32);
+
+impl EffectStacks {
+    #[inline(always)]
+    pub fn count(&self) -> u32 {
+        self.0
+    }
+}
 
 #[derive(SystemParam)]
-pub struct UniqueEffectInterface<'w, 's, E: GameEffect + Component> {
+pub struct EffectInterface<'w, 's, E: StackableEffect> {
     has_effects: Query<'w, 's, &'static HasEffects>,
-    effects_holders: Query<'w, 's, &'static EffectsHolder>,
-    children: Query<'w, 's, &'static Children>,
-    parents: Query<'w, 's, &'static ChildOf>,
+    effect_of: Query<'w, 's, &'static EffectOf>,
     commands: Commands<'w, 's>,
-    effect_query: Query<'w, 's, Entity, With<E>>,
+    effect_query: Query<'w, 's, (Entity, Option<&'static EffectSource>), With<E>>,
+    diminishing_returns: DiminishingReturnsInterface<'w, 's>,
 }
 
-impl<'w, 's, E: GameEffect + Component> UniqueEffectInterface<'w, 's, E> {
-    pub fn spawn_or_replace_unique_effect(&mut self, target: Entity, effect: E) {
-        let effect_e = self
-            .get_unique_effect(target)
-            .unwrap_or_else(|| self.spawn_effect_entity(target));
+impl<'w, 's, E: StackableEffect> EffectInterface<'w, 's, E> {
+    /// Applies `effect` (without [`EffectTimer`]) to `target`, according to the
+    /// [`StackingPolicy`] of `E`. Returns the Effect-`Entity` that holds the effect.
+    pub fn apply_effect(&mut self, target: Entity, source: EffectSource, effect: E) -> Entity {
+        self.apply(target, source, effect, None)
+    }
+
+    /// Applies `effect` with its `timer` to `target`, according to the [`StackingPolicy`] of `E`.
+    /// The timer of debuffs with diminishing returns is shortened accordingly. Returns the
+    /// Effect-`Entity` that holds the effect, or `None` if `target` is immune to it.
+    pub fn apply_timed_effect(
+        &mut self,
+        target: Entity,
+        source: EffectSource,
+        effect: E,
+        timer: EffectTimer,
+    ) -> Option<Entity> {
+        let factor = self
+            .diminishing_returns
+            .register_application(target, E::CLASSIFICATION)
+            .duration_factor()?;
+
+        Some(self.apply(target, source, effect, Some(timer.scaled(factor))))
+    }
+
+    fn apply(
+        &mut self,
+        target: Entity,
+        source: EffectSource,
+        effect: E,
+        timer: Option<EffectTimer>,
+    ) -> Entity {
+        let existing_effect_e = match E::STACKING_POLICY {
+            StackingPolicy::IndependentPerSource => self
+                .effect_query
+                .iter_many(self.get_effect_entities(target))
+                .find(|(_, effect_source)| {
+                    effect_source.is_some_and(|effect_source| effect_source.caster == source.caster)
+                })
+                .map(|(effect_e, _)| effect_e),
+            _ => self.get_effect(target),
+        };
+
+        let Some(effect_e) = existing_effect_e else {
+            let effect_e = self.spawn_effect_entity(target);
+            let mut effect_commands = self.commands.entity(effect_e);
+            effect_commands.insert((effect, source, EffectType::of::<E>(), E::CLASSIFICATION));
+
+            if let Some(detonation) = E::DETONATION {
+                effect_commands.insert(detonation);
+            }
+
+            if let Some(timer) = timer {
+                effect_commands.insert(timer);
+            }
+
+            if let StackingPolicy::AddStack { .. } = E::STACKING_POLICY {
+                effect_commands.insert(EffectStacks(1));
+            }
+
+            self.commands.trigger(EffectApplied {
+                target,
+                effect_entity: effect_e,
+                effect_type: EffectType::of::<E>(),
+                source,
+            });
+
+            return effect_e;
+        };
+
+        let mut effect_commands = self.commands.entity(effect_e);
+
+        match E::STACKING_POLICY {
+            StackingPolicy::Replace | StackingPolicy::IndependentPerSource => {
+                effect_commands
+                    .remove::<(E, EffectTimer)>()
+                    .insert((effect, source));
+
+                if let Some(timer) = timer {
+                    effect_commands.insert(timer);
+                }
+            }
+            StackingPolicy::RefreshDuration => {
+                if let Some(timer) = timer {
+                    effect_commands.insert(timer);
+                }
+            }
+            StackingPolicy::ExtendDuration => {
+                if let Some(timer) = timer {
+                    effect_commands
+                        .entry::<EffectTimer>()
+                        .and_modify(move |mut existing| existing.extend(timer));
+                }
+            }
+            StackingPolicy::AddStack { max_stacks } => {
+                if let Some(timer) = timer {
+                    effect_commands.insert(timer);
+                }
+                effect_commands
+                    .entry::<EffectStacks>()
+                    .and_modify(move |mut stacks| stacks.0 = (stacks.0 + 1).min(max_stacks));
+            }
+        }
+
+        self.commands.trigger(EffectRefreshed {
+            target,
+            effect_entity: effect_e,
+            effect_type: EffectType::of::<E>(),
+            source,
+        });
+
+        effect_e
+    }
+
+    /// Removes a single instance of the effect.
+    pub fn remove_effect(&mut self, effect_e: Entity) {
+        let source = self
+            .effect_query
+            .get(effect_e)
+            .ok()
+            .and_then(|(_, source)| source.copied())
+            .unwrap_or_default();
 
-        self.commands.entity(effect_e).remove::<E>().insert(effect);
+        self.commands.trigger(EffectRemoved {
+            target: self.get_target_of_effect(effect_e),
+            effect_entity: effect_e,
+            effect_type: EffectType::of::<E>(),
+            source,
+        });
+        self.commands.entity(effect_e).despawn();
     }
 
-    /// Returns `true` if `target` had the Effect `E` before, otherwise `false`.
-    pub fn remove_unique_effect(&mut self, target: Entity) -> bool {
-        if let Some(effect_e) = self.get_unique_effect(target) {
-            self.commands.entity(effect_e).despawn();
-            true
-        } else {
-            false
+    /// Removes all instances of the effect from `target`. Returns `true` if `target` had the
+    /// Effect `E` before, otherwise `false`.
+    pub fn remove_effects(&mut self, target: Entity) -> bool {
+        let effect_es = self.get_effects(target).collect::<Vec<_>>();
+
+        for &effect_e in &effect_es {
+            self.remove_effect(effect_e);
         }
+
+        !effect_es.is_empty()
     }
 
-    /// Returns the Effect-`Entity` that has the component `E`, if any.
-    ///
-    /// `panic`s if there is more than one Effect-`Entity` with component `E`.
-    pub fn get_unique_effect(&self, target: Entity) -> Option<Entity> {
-        let effect_es = self.get_effects(target);
+    /// Returns the first Effect-`Entity` that has the component `E`, if any. Unless `E` has
+    /// [`StackingPolicy::IndependentPerSource`], this is the only one.
+    pub fn get_effect(&self, target: Entity) -> Option<Entity> {
+        self.get_effects(target).next()
+    }
+
+    /// Returns all Effect-`Entity`s of `target` that have the component `E`.
+    pub fn get_effects(&self, target: Entity) -> impl Iterator<Item = Entity> {
         self.effect_query
-            .iter_many(effect_es)
-            .at_most_one()
-            .unwrap()
+            .iter_many(self.get_effect_entities(target))
+            .map(|(effect_e, _)| effect_e)
     }
 
     pub fn get_target_of_effect(&self, effect_e: Entity) -> Entity {
-        let holder = self.parents.get(effect_e).unwrap().parent();
-        let effects_holder = self.effects_holders.get(holder).unwrap();
-
-        effects_holder.holding_entity()
+        self.effect_of.get(effect_e).unwrap().target()
     }
 
     fn spawn_effect_entity(&mut self, target: Entity) -> Entity {
-        let holder: Entity = match self.has_effects.get(target) {
-            Ok(he) => he.holder(),
-            Err(_) => {
-                let holder = self.commands.spawn_empty().id();
-                self.commands.entity(target).insert(HasEffects::new(holder));
-                holder
-            }
+        self.commands.spawn(EffectOf(target)).id()
+    }
+
+    fn get_effect_entities(&self, target: Entity) -> &[Entity] {
+        self.has_effects
+            .get(target)
+            .map(HasEffects::effects)
+            .unwrap_or_default()
+    }
+}
+
+/// Access to the effects of a character regardless of their type, see [`EffectInterface`] for
+/// applying and querying effects of a specific type.
+#[derive(SystemParam)]
+pub struct AnyEffectInterface<'w, 's> {
+    has_effects: Query<'w, 's, &'static HasEffects>,
+    effect_of: Query<'w, 's, &'static EffectOf>,
+    commands: Commands<'w, 's>,
+    effect_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static EffectType,
+            &'static EffectSource,
+            &'static EffectClassification,
+        ),
+    >,
+    detonations: Query<'w, 's, &'static DelayedDetonation>,
+}
+
+impl<'w, 's> AnyEffectInterface<'w, 's> {
+    /// Returns all Effect-`Entity`s of `target`, in the order they were applied.
+    pub fn get_effects(&self, target: Entity) -> impl Iterator<Item = Entity> {
+        self.effect_query
+            .iter_many(self.get_effect_entities(target))
+            .map(|(effect_e, ..)| effect_e)
+    }
+
+    pub fn get_classification(&self, effect_e: Entity) -> Option<&EffectClassification> {
+        self.effect_query
+            .get(effect_e)
+            .ok()
+            .map(|(.., classification)| classification)
+    }
+
+    /// Removes a single effect, whatever its type.
+    pub fn remove_effect(&mut self, effect_e: Entity) {
+        let Ok((_, effect_type, source, _)) = self.effect_query.get(effect_e) else {
+            return;
         };
 
-        let new_effect = self.commands.spawn_empty().id();
-        self.commands.entity(holder).add_child(new_effect);
+        self.commands.trigger(EffectRemoved {
+            target: self.effect_of.get(effect_e).unwrap().target(),
+            effect_entity: effect_e,
+            effect_type: *effect_type,
+            source: *source,
+        });
+        self.commands.entity(effect_e).despawn();
+    }
+
+    /// Removes up to `max_count` dispellable effects of `disposition` and one of `categories`
+    /// from `target`, the most recently applied first. Returns the number of removed effects.
+    pub fn dispel(
+        &mut self,
+        target: Entity,
+        disposition: EffectDisposition,
+        categories: &[EffectCategory],
+        max_count: u32,
+    ) -> u32 {
+        let dispelled = self
+            .effect_query
+            .iter_many(self.get_effect_entities(target))
+            .filter(|(.., classification)| {
+                classification.dispellable
+                    && classification.disposition == disposition
+                    && categories.contains(&classification.category)
+            })
+            .map(|(effect_e, ..)| effect_e)
+            .collect::<Vec<_>>();
+
+        let mut count = 0;
+        for effect_e in dispelled.into_iter().rev().take(max_count as usize) {
+            if self
+                .detonations
+                .get(effect_e)
+                .is_ok_and(|detonation| detonation.detonate_on_dispel)
+            {
+                self.trigger_detonation(effect_e, DetonationReason::Dispelled);
+            }
 
-        new_effect
+            self.remove_effect(effect_e);
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Detonates a [`DelayedDetonation`] effect early and removes it. Returns `false` if the
+    /// effect doesn't detonate.
+    pub fn detonate(&mut self, effect_e: Entity) -> bool {
+        if !self.detonations.contains(effect_e) {
+            return false;
+        }
+
+        self.trigger_detonation(effect_e, DetonationReason::Triggered);
+        self.remove_effect(effect_e);
+
+        true
     }
 
-    fn get_effects(&self, target: Entity) -> &[Entity] {
-        let Ok(holder) = self.has_effects.get(target).map(|he| he.holder()) else {
-            return &[];
+    fn trigger_detonation(&mut self, effect_e: Entity, reason: DetonationReason) {
+        let Ok((_, _, source, _)) = self.effect_query.get(effect_e) else {
+            return;
         };
 
-        match self.children.get(holder) {
-            Ok(children) => children,
-            Err(_) => &[],
+        self.commands.trigger(EffectDetonated {
+            target: self.effect_of.get(effect_e).unwrap().target(),
+            effect_entity: effect_e,
+            source: *source,
+            reason,
+        });
+    }
+
+    fn get_effect_entities(&self, target: Entity) -> &[Entity] {
+        self.has_effects
+            .get(target)
+            .map(HasEffects::effects)
+            .unwrap_or_default()
+    }
+}
+
+/// Ticks all [`EffectTimer`]s of characters in unpaused fights, and removes finished effects.
+fn tick_effect_timers(
+    mut effect_timers: Query<(
+        Entity,
+        &mut EffectTimer,
+        &EffectType,
+        &EffectSource,
+        &EffectOf,
+    )>,
+    detonations: Query<(), With<DelayedDetonation>>,
+    fight_interface: FightInterface,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (effect_e, mut timer, effect_type, source, effect_of) in &mut effect_timers {
+        let target = effect_of.target();
+
+        if fight_interface.is_fight_paused(fight_interface.get_fight_of_entity(target)) {
+            continue;
+        }
+
+        let ticks = timer.tick(time.delta());
+
+        if ticks > 0 {
+            commands.trigger(EffectTicked {
+                target,
+                effect_entity: effect_e,
+                ticks,
+            });
+        }
+
+        if timer.is_finished() {
+            if detonations.contains(effect_e) {
+                commands.trigger(EffectDetonated {
+                    target,
+                    effect_entity: effect_e,
+                    source: *source,
+                    reason: DetonationReason::Expired,
+                });
+            }
+
+            commands.trigger(EffectExpired {
+                target,
+                effect_entity: effect_e,
+                effect_type: *effect_type,
+                source: *source,
+            });
+            commands.entity(effect_e).despawn();
         }
     }
 }
@@ -120,35 +751,466 @@ pub struct EffectsPlugin;
 impl Plugin for EffectsPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<HasEffects>()
-            .register_type::<EffectsHolder>()
-            .add_observer(on_add_has_effects)
-            .add_observer(on_remove_has_effects);
+            .register_type::<EffectOf>()
+            .register_type::<EffectSource>()
+            .register_type::<EffectClassification>()
+            .register_type::<DelayedDetonation>()
+            .register_type::<DetonationReason>()
+            .register_type::<EffectStacks>()
+            .register_type::<EffectTimer>()
+            .register_type::<EffectType>()
+            .add_plugins(DiminishingReturnsPlugin)
+            .add_systems(
+                FixedUpdate,
+                tick_effect_timers.in_set(PerUpdateSet::LogicUpdate),
+            );
     }
 }
 
-fn on_add_has_effects(
-    trigger: On<Add, HasEffects>,
-    has_effects: Query<&HasEffects>,
-    effects_holder: Query<Entity, With<EffectsHolder>>,
-    mut commands: Commands,
-) {
-    let holding_entity = trigger.entity;
-    let holder = has_effects.get(holding_entity).unwrap().holder;
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
 
-    // assert that the holder doesn't have `EffectsHolder` yet
-    assert!(effects_holder.get(holder).is_err());
+    use bevy::{ecs::system::RunSystemOnce, prelude::*, time::TimeUpdateStrategy};
 
-    commands
-        .entity(holder)
-        .insert(EffectsHolder::new(holding_entity));
-}
+    use super::{
+        AnyEffectInterface, DelayedDetonation, DetonationReason, EffectApplied, EffectCategory,
+        EffectClassification, EffectDetonated, EffectDisposition, EffectExpired, EffectInterface,
+        EffectOf, EffectRefreshed, EffectRemoved, EffectSource, EffectStacks, EffectTicked,
+        EffectTimer, EffectType, EffectsPlugin, GameEffect, StackableEffect, StackingPolicy,
+    };
+    use crate::{
+        game_logic::{
+            commands::CommandsPlugin,
+            fight::{FightPlugin, FightTime},
+        },
+        test_utils::{TestFightEntities, spawn_test_fight},
+    };
 
-fn on_remove_has_effects(
-    trigger: On<Remove, HasEffects>,
-    has_effects: Query<&HasEffects>,
-    mut commands: Commands,
-) {
-    let holder = has_effects.get(trigger.entity).unwrap().holder;
+    #[derive(Debug, Component, Reflect)]
+    struct StackingTestEffect;
+
+    impl GameEffect for StackingTestEffect {}
+
+    impl StackableEffect for StackingTestEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::AddStack { max_stacks: 3 };
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::buff(EffectCategory::Magic);
+    }
+
+    #[derive(Debug, Component, Reflect)]
+    struct PerSourceTestEffect;
+
+    impl GameEffect for PerSourceTestEffect {}
+
+    impl StackableEffect for PerSourceTestEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::IndependentPerSource;
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::buff(EffectCategory::Magic);
+    }
+
+    #[derive(Debug, Component, Reflect)]
+    struct PeriodicTestEffect;
+
+    impl GameEffect for PeriodicTestEffect {}
+
+    impl StackableEffect for PeriodicTestEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::Replace;
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::buff(EffectCategory::Magic);
+    }
+
+    #[derive(Debug, Component, Reflect)]
+    struct MagicDebuffTestEffect;
+
+    impl GameEffect for MagicDebuffTestEffect {}
+
+    impl StackableEffect for MagicDebuffTestEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::Replace;
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::debuff(EffectCategory::Magic);
+    }
 
-    commands.entity(holder).despawn();
+    #[derive(Debug, Component, Reflect)]
+    struct UndispellableDebuffTestEffect;
+
+    impl GameEffect for UndispellableDebuffTestEffect {}
+
+    impl StackableEffect for UndispellableDebuffTestEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::Replace;
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::debuff(EffectCategory::Magic).undispellable();
+    }
+
+    #[derive(Debug, Component, Reflect)]
+    struct CurseTestEffect;
+
+    impl GameEffect for CurseTestEffect {}
+
+    impl StackableEffect for CurseTestEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::Replace;
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::debuff(EffectCategory::Curse);
+    }
+
+    #[derive(Debug, Component, Reflect)]
+    struct BombTestEffect;
+
+    impl GameEffect for BombTestEffect {}
+
+    impl StackableEffect for BombTestEffect {
+        const STACKING_POLICY: StackingPolicy = StackingPolicy::IndependentPerSource;
+        const CLASSIFICATION: EffectClassification =
+            EffectClassification::debuff(EffectCategory::Magic);
+        const DETONATION: Option<DelayedDetonation> = Some(DelayedDetonation {
+            detonate_on_dispel: true,
+        });
+    }
+
+    fn apply<E: StackableEffect>(
+        app: &mut App,
+        target: Entity,
+        source: Option<Entity>,
+        effect: impl Fn() -> E + Send + Sync + 'static,
+    ) {
+        app.world_mut()
+            .run_system_once(move |mut effects_interface: EffectInterface<E>| {
+                effects_interface.apply_effect(
+                    target,
+                    EffectSource {
+                        caster: source,
+                        ability: None,
+                    },
+                    effect(),
+                );
+            })
+            .unwrap();
+    }
+
+    fn effects_of<E: StackableEffect>(app: &mut App, target: Entity) -> Vec<Entity> {
+        app.world_mut()
+            .run_system_once(move |effects_interface: EffectInterface<E>| {
+                effects_interface.get_effects(target).collect::<Vec<_>>()
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_add_stack_is_capped_at_max_stacks() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_plugins(EffectsPlugin);
+
+        let target = app.world_mut().spawn_empty().id();
+
+        for _ in 0..5 {
+            apply(&mut app, target, None, || StackingTestEffect);
+        }
+
+        let effects = effects_of::<StackingTestEffect>(&mut app, target);
+        assert_eq!(effects.len(), 1);
+        assert_eq!(
+            app.world().get::<EffectStacks>(effects[0]),
+            Some(&EffectStacks(3))
+        );
+    }
+
+    #[test]
+    fn test_independent_instances_per_source() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_plugins(EffectsPlugin);
+
+        let target = app.world_mut().spawn_empty().id();
+        let source_a = app.world_mut().spawn_empty().id();
+        let source_b = app.world_mut().spawn_empty().id();
+
+        apply(&mut app, target, Some(source_a), || PerSourceTestEffect);
+        apply(&mut app, target, Some(source_b), || PerSourceTestEffect);
+        apply(&mut app, target, Some(source_a), || PerSourceTestEffect);
+
+        assert_eq!(effects_of::<PerSourceTestEffect>(&mut app, target).len(), 2);
+    }
+
+    #[test]
+    fn test_effects_are_despawned_with_their_target() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_plugins(EffectsPlugin);
+
+        let target = app.world_mut().spawn_empty().id();
+        apply(&mut app, target, None, || PerSourceTestEffect);
+
+        let effect_e = effects_of::<PerSourceTestEffect>(&mut app, target)[0];
+        assert_eq!(
+            app.world().get::<EffectOf>(effect_e).unwrap().target(),
+            target
+        );
+
+        app.world_mut().despawn(target);
+
+        assert!(app.world().get_entity(effect_e).is_err());
+    }
+
+    #[test]
+    fn test_periodic_effect_ticks_and_expires() {
+        #[derive(Resource, Default)]
+        struct Observed {
+            ticks: u32,
+            expired: u32,
+        }
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .init_resource::<Observed>()
+            .add_observer(
+                |trigger: On<EffectTicked>, mut observed: ResMut<Observed>| {
+                    observed.ticks += trigger.event().ticks;
+                },
+            )
+            .add_observer(|_: On<EffectExpired>, mut observed: ResMut<Observed>| {
+                observed.expired += 1;
+            });
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            slot_e: _,
+            ability_e: _,
+            enemy_e: _,
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+
+        app.world_mut()
+            .run_system_once(
+                move |mut effects_interface: EffectInterface<PeriodicTestEffect>| {
+                    effects_interface.apply_timed_effect(
+                        caster_e,
+                        EffectSource::default(),
+                        PeriodicTestEffect,
+                        EffectTimer::periodic(Duration::from_millis(50), 2),
+                    );
+                },
+            )
+            .unwrap();
+
+        // update once to initialize all systems etc., required when testing with manual time.
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            65,
+        )));
+
+        app.update();
+        assert_eq!(app.world().resource::<Observed>().ticks, 1);
+        assert_eq!(app.world().resource::<Observed>().expired, 0);
+
+        app.update();
+        assert_eq!(app.world().resource::<Observed>().ticks, 2);
+        assert_eq!(app.world().resource::<Observed>().expired, 1);
+        assert!(effects_of::<PeriodicTestEffect>(&mut app, caster_e).is_empty());
+    }
+
+    #[test]
+    fn test_lifecycle_events_are_triggered_on_the_target() {
+        #[derive(Resource, Default)]
+        struct Observed(Vec<(&'static str, Entity, EffectType, Option<Entity>)>);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(EffectsPlugin)
+            .init_resource::<Observed>()
+            .add_observer(
+                |trigger: On<EffectApplied>, mut observed: ResMut<Observed>| {
+                    let event = trigger.event();
+                    observed.0.push((
+                        "applied",
+                        event.target,
+                        event.effect_type,
+                        event.source.caster,
+                    ));
+                },
+            )
+            .add_observer(
+                |trigger: On<EffectRefreshed>, mut observed: ResMut<Observed>| {
+                    let event = trigger.event();
+                    observed.0.push((
+                        "refreshed",
+                        event.target,
+                        event.effect_type,
+                        event.source.caster,
+                    ));
+                },
+            )
+            .add_observer(
+                |trigger: On<EffectRemoved>, mut observed: ResMut<Observed>| {
+                    let event = trigger.event();
+                    observed.0.push((
+                        "removed",
+                        event.target,
+                        event.effect_type,
+                        event.source.caster,
+                    ));
+                },
+            );
+
+        let target = app.world_mut().spawn_empty().id();
+        let source = app.world_mut().spawn_empty().id();
+
+        apply(&mut app, target, Some(source), || StackingTestEffect);
+        apply(&mut app, target, Some(source), || StackingTestEffect);
+        app.world_mut()
+            .run_system_once(
+                move |mut effects_interface: EffectInterface<StackingTestEffect>| {
+                    effects_interface.remove_effects(target)
+                },
+            )
+            .unwrap();
+
+        let effect_type = EffectType::of::<StackingTestEffect>();
+        assert!(effect_type.is::<StackingTestEffect>());
+        assert_eq!(effect_type.name(), "StackingTestEffect");
+        assert_eq!(
+            app.world().resource::<Observed>().0,
+            [
+                ("applied", target, effect_type, Some(source)),
+                ("refreshed", target, effect_type, Some(source)),
+                ("removed", target, effect_type, Some(source)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dispel_removes_only_matching_dispellable_effects() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_plugins(EffectsPlugin);
+
+        let target = app.world_mut().spawn_empty().id();
+
+        apply(&mut app, target, None, || StackingTestEffect);
+        apply(&mut app, target, None, || MagicDebuffTestEffect);
+        apply(&mut app, target, None, || UndispellableDebuffTestEffect);
+        apply(&mut app, target, None, || CurseTestEffect);
+
+        let dispelled = app
+            .world_mut()
+            .run_system_once(move |mut any_effect_interface: AnyEffectInterface| {
+                any_effect_interface.dispel(
+                    target,
+                    EffectDisposition::Debuff,
+                    &[EffectCategory::Magic],
+                    5,
+                )
+            })
+            .unwrap();
+        app.world_mut().flush();
+
+        assert_eq!(dispelled, 1);
+        assert!(effects_of::<MagicDebuffTestEffect>(&mut app, target).is_empty());
+        assert_eq!(effects_of::<StackingTestEffect>(&mut app, target).len(), 1);
+        assert_eq!(
+            effects_of::<UndispellableDebuffTestEffect>(&mut app, target).len(),
+            1
+        );
+        assert_eq!(effects_of::<CurseTestEffect>(&mut app, target).len(), 1);
+    }
+
+    #[test]
+    fn test_delayed_detonation_on_expiry_dispel_and_trigger() {
+        #[derive(Resource, Default)]
+        struct Detonations(Vec<(Option<Entity>, DetonationReason)>);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .init_resource::<Detonations>()
+            .add_observer(
+                |trigger: On<EffectDetonated>,
+                 bombs: Query<(), With<BombTestEffect>>,
+                 mut detonations: ResMut<Detonations>| {
+                    let event = trigger.event();
+                    // the effect still exists, so its payload can be read
+                    assert!(bombs.contains(event.effect_entity));
+                    detonations.0.push((event.source.caster, event.reason));
+                },
+            );
+
+        let TestFightEntities {
+            fight_e,
+            caster_e,
+            enemy_e,
+            ..
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+
+        let [source_a, source_b, source_c] = [(); 3].map(|_| app.world_mut().spawn_empty().id());
+
+        app.world_mut()
+            .run_system_once(
+                move |mut effects_interface: EffectInterface<BombTestEffect>| {
+                    effects_interface.apply_timed_effect(
+                        caster_e,
+                        EffectSource {
+                            caster: Some(source_a),
+                            ability: None,
+                        },
+                        BombTestEffect,
+                        EffectTimer::duration(Duration::from_millis(100)),
+                    );
+                },
+            )
+            .unwrap();
+        apply(&mut app, caster_e, Some(source_b), || BombTestEffect);
+        apply(&mut app, caster_e, Some(source_c), || BombTestEffect);
+        // bombs on other characters are unaffected
+        apply(&mut app, enemy_e, Some(source_c), || BombTestEffect);
+
+        // the most recent bomb detonates when dispelled
+        app.world_mut()
+            .run_system_once(move |mut any_effect_interface: AnyEffectInterface| {
+                any_effect_interface.dispel(
+                    caster_e,
+                    EffectDisposition::Debuff,
+                    &[EffectCategory::Magic],
+                    1,
+                );
+            })
+            .unwrap();
+
+        let bomb_b = effects_of::<BombTestEffect>(&mut app, caster_e)[1];
+        let detonated = app
+            .world_mut()
+            .run_system_once(move |mut any_effect_interface: AnyEffectInterface| {
+                any_effect_interface.detonate(bomb_b)
+            })
+            .unwrap();
+        assert!(detonated);
+
+        // update once to initialize all systems etc., required when testing with manual time.
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            150,
+        )));
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<Detonations>().0,
+            [
+                (Some(source_c), DetonationReason::Dispelled),
+                (Some(source_b), DetonationReason::Triggered),
+                (Some(source_a), DetonationReason::Expired),
+            ]
+        );
+        assert!(effects_of::<BombTestEffect>(&mut app, caster_e).is_empty());
+        assert_eq!(effects_of::<BombTestEffect>(&mut app, enemy_e).len(), 1);
+    }
 }
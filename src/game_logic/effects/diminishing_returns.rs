@@ -0,0 +1,341 @@
+use std::time::Duration;
+
+use bevy::{ecs::system::SystemParam, platform::collections::HashMap, prelude::*};
+
+use crate::{
+    PerUpdateSet,
+    game_logic::{
+        effects::{EffectCategory, EffectClassification, EffectDisposition, EffectExpired},
+        fight::FightInterface,
+    },
+};
+
+/// Configures how repeated debuffs with [`EffectClassification::diminishing_returns`] lose
+/// effectiveness on the same target.
+#[derive(Debug, Clone, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct DiminishingReturnsSettings {
+    /// The diminishing returns of a category reset once no debuff of it was applied for this
+    /// long.
+    pub reset_window: Duration,
+    /// How long a target is immune against debuffs of a category after one of them expired.
+    pub immunity_window: Duration,
+}
+
+impl Default for DiminishingReturnsSettings {
+    fn default() -> Self {
+        Self {
+            reset_window: Duration::from_secs(15),
+            immunity_window: Duration::from_secs(2),
+        }
+    }
+}
+
+/// How effective a debuff is, depending on how often debuffs of its category were applied to the
+/// target within the [`DiminishingReturnsSettings::reset_window`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum DiminishingReturnsLevel {
+    Full,
+    Half,
+    Quarter,
+    Immune,
+}
+
+impl DiminishingReturnsLevel {
+    fn from_previous_applications(count: u32) -> Self {
+        match count {
+            0 => DiminishingReturnsLevel::Full,
+            1 => DiminishingReturnsLevel::Half,
+            2 => DiminishingReturnsLevel::Quarter,
+            _ => DiminishingReturnsLevel::Immune,
+        }
+    }
+
+    /// The factor for the duration of the debuff, `None` if the target is immune.
+    pub fn duration_factor(&self) -> Option<f64> {
+        match self {
+            DiminishingReturnsLevel::Full => Some(1.0),
+            DiminishingReturnsLevel::Half => Some(0.5),
+            DiminishingReturnsLevel::Quarter => Some(0.25),
+            DiminishingReturnsLevel::Immune => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Reflect)]
+struct CategoryReturns {
+    applications: u32,
+    since_last_application: Duration,
+}
+
+/// The diminishing returns and immunity windows of a character, per [`EffectCategory`].
+/// Inserted when the first debuff with diminishing returns is applied to it.
+#[derive(Debug, Clone, Default, Component, Reflect)]
+pub struct DiminishingReturns {
+    returns: HashMap<EffectCategory, CategoryReturns>,
+    immunities: HashMap<EffectCategory, Timer>,
+}
+
+impl DiminishingReturns {
+    /// The level the next debuff of `category` would be applied with.
+    pub fn level(&self, category: EffectCategory) -> DiminishingReturnsLevel {
+        if self.is_immune(category) {
+            return DiminishingReturnsLevel::Immune;
+        }
+
+        DiminishingReturnsLevel::from_previous_applications(
+            self.returns
+                .get(&category)
+                .map_or(0, |returns| returns.applications),
+        )
+    }
+
+    /// Whether the character is in an immunity window for `category`.
+    pub fn is_immune(&self, category: EffectCategory) -> bool {
+        self.immunities.contains_key(&category)
+    }
+
+    fn register_application(&mut self, category: EffectCategory) {
+        let returns = self.returns.entry(category).or_default();
+
+        returns.applications += 1;
+        returns.since_last_application = Duration::ZERO;
+    }
+
+    fn tick(&mut self, delta: Duration, reset_window: Duration) {
+        self.returns.retain(|_, returns| {
+            returns.since_last_application += delta;
+            returns.since_last_application < reset_window
+        });
+        self.immunities.retain(|_, immunity| {
+            immunity.tick(delta);
+            !immunity.is_finished()
+        });
+    }
+}
+
+#[derive(SystemParam)]
+pub struct DiminishingReturnsInterface<'w, 's> {
+    // read-only, so multiple `EffectInterface`s can be used in the same system
+    diminishing_returns: Query<'w, 's, &'static DiminishingReturns>,
+    commands: Commands<'w, 's>,
+}
+
+impl<'w, 's> DiminishingReturnsInterface<'w, 's> {
+    /// Registers the application of an effect with `classification` to `target`, and returns the
+    /// level it has to be applied with. Effects that are no debuffs with diminishing returns are
+    /// always applied fully.
+    ///
+    /// The application is registered via [`Commands`], so applications within the same system
+    /// all get the same level.
+    pub fn register_application(
+        &mut self,
+        target: Entity,
+        classification: EffectClassification,
+    ) -> DiminishingReturnsLevel {
+        if !classification.diminishing_returns
+            || classification.disposition != EffectDisposition::Debuff
+        {
+            return DiminishingReturnsLevel::Full;
+        }
+
+        let level = self
+            .diminishing_returns
+            .get(target)
+            .map_or(DiminishingReturnsLevel::Full, |diminishing_returns| {
+                diminishing_returns.level(classification.category)
+            });
+
+        if level != DiminishingReturnsLevel::Immune {
+            self.commands
+                .entity(target)
+                .entry::<DiminishingReturns>()
+                .or_default()
+                .and_modify(move |mut diminishing_returns| {
+                    diminishing_returns.register_application(classification.category);
+                });
+        }
+
+        level
+    }
+}
+
+/// Ticks the diminishing returns and immunity windows of characters in unpaused fights.
+fn tick_diminishing_returns(
+    mut diminishing_returns: Query<(Entity, &mut DiminishingReturns)>,
+    fight_interface: FightInterface,
+    settings: Res<DiminishingReturnsSettings>,
+    time: Res<Time>,
+) {
+    for (character, mut diminishing_returns) in &mut diminishing_returns {
+        if fight_interface.is_fight_paused(fight_interface.get_fight_of_entity(character)) {
+            continue;
+        }
+
+        diminishing_returns.tick(time.delta(), settings.reset_window);
+    }
+}
+
+/// Starts the immunity window for the category of an expired debuff with diminishing returns.
+fn start_immunity_window(
+    trigger: On<EffectExpired>,
+    classifications: Query<&EffectClassification>,
+    mut diminishing_returns: Query<&mut DiminishingReturns>,
+    settings: Res<DiminishingReturnsSettings>,
+) {
+    let event = trigger.event();
+
+    let Ok(classification) = classifications.get(event.effect_entity) else {
+        return;
+    };
+
+    if !classification.diminishing_returns
+        || classification.disposition != EffectDisposition::Debuff
+        || settings.immunity_window.is_zero()
+    {
+        return;
+    }
+
+    // debuffs with diminishing returns insert the component when they are applied
+    let Ok(mut diminishing_returns) = diminishing_returns.get_mut(event.target) else {
+        return;
+    };
+
+    diminishing_returns.immunities.insert(
+        classification.category,
+        Timer::new(settings.immunity_window, TimerMode::Once),
+    );
+}
+
+#[derive(Debug)]
+pub struct DiminishingReturnsPlugin;
+
+impl Plugin for DiminishingReturnsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<DiminishingReturnsSettings>()
+            .register_type::<DiminishingReturns>()
+            .init_resource::<DiminishingReturnsSettings>()
+            .add_systems(
+                FixedUpdate,
+                tick_diminishing_returns.in_set(PerUpdateSet::LogicUpdate),
+            )
+            .add_observer(start_immunity_window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{ecs::system::RunSystemOnce, prelude::*, time::TimeUpdateStrategy};
+
+    use super::DiminishingReturnsSettings;
+    use crate::{
+        game_logic::{
+            commands::CommandsPlugin,
+            crowd_control::{CrowdControlEffect, SilenceEffect, StunEffect},
+            effects::{EffectInterface, EffectSource, EffectTimer, EffectsPlugin},
+            fight::{FightPlugin, FightTime},
+        },
+        test_utils::{TestFightEntities, spawn_test_fight},
+    };
+
+    /// Returns the duration the effect was applied with, `None` if the target was immune.
+    fn apply<E: CrowdControlEffect>(
+        app: &mut App,
+        target: Entity,
+        duration: Duration,
+    ) -> Option<Duration> {
+        let effect_e = app
+            .world_mut()
+            .run_system_once(move |mut effects_interface: EffectInterface<E>| {
+                effects_interface.apply_timed_effect(
+                    target,
+                    EffectSource::default(),
+                    E::default(),
+                    EffectTimer::duration(duration),
+                )
+            })
+            .unwrap()?;
+
+        Some(
+            app.world()
+                .get::<EffectTimer>(effect_e)
+                .unwrap()
+                .remaining(),
+        )
+    }
+
+    #[test]
+    fn test_repeated_debuffs_diminish_and_expiry_grants_immunity() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(EffectsPlugin)
+            .insert_resource(DiminishingReturnsSettings {
+                reset_window: Duration::from_secs(5),
+                immunity_window: Duration::from_secs(1),
+            });
+
+        let TestFightEntities {
+            fight_e, caster_e, ..
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut()
+            .get_mut::<FightTime>(fight_e)
+            .unwrap()
+            .set_paused(false);
+
+        // update once to initialize all systems etc., required when testing with manual time.
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+
+        let secs = Duration::from_secs_f64;
+        assert_eq!(
+            apply::<StunEffect>(&mut app, caster_e, secs(2.0)),
+            Some(secs(2.0))
+        );
+        assert_eq!(
+            apply::<StunEffect>(&mut app, caster_e, secs(2.0)),
+            Some(secs(1.0))
+        );
+        assert_eq!(
+            apply::<StunEffect>(&mut app, caster_e, secs(2.0)),
+            Some(secs(0.5))
+        );
+        assert_eq!(apply::<StunEffect>(&mut app, caster_e, secs(2.0)), None);
+
+        // other categories are tracked separately
+        assert_eq!(
+            apply::<SilenceEffect>(&mut app, caster_e, secs(0.2)),
+            Some(secs(0.2))
+        );
+
+        // the silence expired, the stun is still active
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(apply::<SilenceEffect>(&mut app, caster_e, secs(2.0)), None);
+
+        // after the immunity window, diminishing returns still apply
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(
+            apply::<SilenceEffect>(&mut app, caster_e, secs(2.0)),
+            Some(secs(1.0))
+        );
+
+        // the stun expired as well, but the reset window has passed since its last application
+        for _ in 0..50 {
+            app.update();
+        }
+        assert_eq!(
+            apply::<StunEffect>(&mut app, caster_e, secs(2.0)),
+            Some(secs(2.0))
+        );
+    }
+}
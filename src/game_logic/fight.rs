@@ -151,6 +151,7 @@ fn single_faction_survives_check(
     for liveness_change in liveness_events.read() {
         let died_entity = match liveness_change {
             LivenessChangeEvent::EntityDied { which } => *which,
+            LivenessChangeEvent::EntityResurrected { .. } => continue,
         };
 
         let fight_e = parents.get(died_entity).unwrap().parent();
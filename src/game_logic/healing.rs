@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+
+use super::health::{HealthInterface, HpGain};
+use crate::{
+    PerUpdateSet,
+    game_logic::{
+        character_stats::{CharacterStat, CharacterStatsInterface},
+        damage_resolution::DamageResolutionStep,
+    },
+};
+
+#[derive(Debug, Clone, Reflect, PartialEq)]
+pub struct HealInstance {
+    /// The character that caused the heal, if any.
+    pub source: Option<Entity>,
+    /// The ability that caused the heal, directly or through an effect it applied.
+    pub source_ability: Option<Entity>,
+    pub target: Entity,
+    pub amount: f64,
+    /// Whether this heal brings a dead target back to life. Otherwise, heals on dead targets are
+    /// rejected.
+    pub resurrect: bool,
+}
+
+#[derive(Event, Message, Debug, Clone)]
+pub struct Heal(pub HealInstance);
+
+/// The result of a resolved [`Heal`]. Not sent for rejected heals.
+#[derive(Message, Debug, Clone)]
+pub struct HealingDone {
+    pub instance: HealInstance,
+    /// The amount of health that was actually gained.
+    pub applied: f64,
+    /// The amount exceeding the target's maximum health.
+    pub overheal: f64,
+}
+
+/// Resolves all heals of this update, before any damage is resolved.
+fn heal_resolution_system(
+    mut heal_events: MessageReader<Heal>,
+    mut health_interface: HealthInterface,
+    mut healing_done_events: MessageWriter<HealingDone>,
+    character_stats: CharacterStatsInterface,
+) {
+    for heal_event in heal_events.read() {
+        let heal = HealInstance {
+            amount: character_stats.modified(
+                heal_event.0.target,
+                CharacterStat::HealingReceived,
+                heal_event.0.amount,
+            ),
+            ..heal_event.0.clone()
+        };
+
+        let is_dead = health_interface
+            .healths()
+            .get(heal.target)
+            .unwrap()
+            .is_dead();
+
+        let gain = if is_dead && heal.resurrect {
+            health_interface.resurrect(heal.target, heal.amount).ok()
+        } else {
+            health_interface.gain_hp(heal.target, heal.amount).ok()
+        };
+
+        let Some(HpGain { applied, overheal }) = gain else {
+            debug!("Rejected heal on dead target: {heal:?}");
+            continue;
+        };
+
+        healing_done_events.write(HealingDone {
+            instance: heal,
+            applied,
+            overheal,
+        });
+    }
+}
+
+pub struct HealingPlugin;
+
+impl Plugin for HealingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HealInstance>()
+            .add_message::<Heal>()
+            .add_message::<HealingDone>()
+            .add_systems(
+                Update,
+                heal_resolution_system
+                    .in_set(PerUpdateSet::DamageResolution)
+                    .before(DamageResolutionStep::Collect),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::{Heal, HealInstance, HealingDone, HealingPlugin};
+    use crate::game_logic::{
+        damage_resolution::{DamageInstance, DamageResolutionPlugin, DamageType, DealDamage},
+        health::{Health, HealthInterfacePlugin},
+    };
+
+    fn heal(app: &mut App, target: Entity, amount: f64, resurrect: bool) {
+        app.world_mut().write_message(Heal(HealInstance {
+            source: None,
+            source_ability: None,
+            target,
+            amount,
+            resurrect,
+        }));
+    }
+
+    fn current_hp(app: &App, target: Entity) -> f64 {
+        app.world().get::<Health>(target).unwrap().current()
+    }
+
+    #[test]
+    fn test_heal_clamps_and_rejects_dead_targets() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(HealthInterfacePlugin)
+            .add_plugins(DamageResolutionPlugin)
+            .add_plugins(HealingPlugin);
+
+        let wounded = app.world_mut().spawn(Health::new(100.0)).id();
+        let dead = app.world_mut().spawn(Health::new(100.0)).id();
+
+        for (target, amount) in [(wounded, 30.0), (dead, 150.0)] {
+            app.world_mut().write_message(DealDamage(DamageInstance {
+                source: None,
+                source_ability: None,
+                target,
+                amount,
+                damage_type: DamageType::Physical,
+            }));
+        }
+        app.update();
+
+        heal(&mut app, wounded, 50.0, false);
+        heal(&mut app, dead, 50.0, false);
+        app.update();
+
+        assert_eq!(current_hp(&app, wounded), 100.0);
+        assert_eq!(current_hp(&app, dead), -50.0);
+
+        let messages = app.world().resource::<Messages<HealingDone>>();
+        let healing_done = messages.iter_current_update_messages().collect::<Vec<_>>();
+        assert_eq!(healing_done.len(), 1);
+        assert_eq!(healing_done[0].applied, 30.0);
+        assert_eq!(healing_done[0].overheal, 20.0);
+
+        heal(&mut app, dead, 50.0, true);
+        app.update();
+
+        assert_eq!(current_hp(&app, dead), 50.0);
+    }
+}
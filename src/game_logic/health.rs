@@ -34,9 +34,29 @@ impl Health {
 #[derive(Debug)]
 pub struct AlreadyDeadError;
 
+#[derive(Debug)]
+pub struct NotDeadError;
+
+#[derive(Debug)]
+pub enum LoseHpError {
+    /// The target has no [`Health`], e.g., because it was despawned.
+    NoHealth,
+    AlreadyDead,
+}
+
 #[derive(Debug, Clone, Event, Message)]
 pub enum LivenessChangeEvent {
     EntityDied { which: Entity },
+    EntityResurrected { which: Entity },
+}
+
+/// The result of healing an entity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HpGain {
+    /// The amount of health that was actually gained.
+    pub applied: f64,
+    /// The amount exceeding the entity's maximum health.
+    pub overheal: f64,
 }
 
 #[derive(SystemParam)]
@@ -46,8 +66,10 @@ pub struct HealthInterface<'w, 's> {
 }
 
 impl<'w, 's> HealthInterface<'w, 's> {
-    pub fn lose_hp(&mut self, target: Entity, amount: f64) -> Result<(), AlreadyDeadError> {
-        let mut target_health = self.healths.get_mut(target).unwrap();
+    pub fn lose_hp(&mut self, target: Entity, amount: f64) -> Result<(), LoseHpError> {
+        let Ok(mut target_health) = self.healths.get_mut(target) else {
+            return Err(LoseHpError::NoHealth);
+        };
 
         if target_health.is_alive() {
             target_health.current -= amount;
@@ -59,7 +81,47 @@ impl<'w, 's> HealthInterface<'w, 's> {
 
             Ok(())
         } else {
-            Err(AlreadyDeadError)
+            Err(LoseHpError::AlreadyDead)
+        }
+    }
+
+    /// Heals `target` by `amount`, clamped to its maximum health.
+    pub fn gain_hp(&mut self, target: Entity, amount: f64) -> Result<HpGain, AlreadyDeadError> {
+        let mut target_health = self.healths.get_mut(target).unwrap();
+
+        if target_health.is_dead() {
+            return Err(AlreadyDeadError);
+        }
+
+        Ok(Self::clamped_gain(&mut target_health, amount))
+    }
+
+    /// Brings a dead `target` back to life with `amount` health, clamped to its maximum health.
+    pub fn resurrect(&mut self, target: Entity, amount: f64) -> Result<HpGain, NotDeadError> {
+        let mut target_health = self.healths.get_mut(target).unwrap();
+
+        if target_health.is_alive() {
+            return Err(NotDeadError);
+        }
+
+        target_health.current = 0.0;
+        let gain = Self::clamped_gain(&mut target_health, amount);
+
+        if target_health.is_alive() {
+            self.liveness_events
+                .write(LivenessChangeEvent::EntityResurrected { which: target });
+        }
+
+        Ok(gain)
+    }
+
+    fn clamped_gain(health: &mut Health, amount: f64) -> HpGain {
+        let applied = amount.min(health.max - health.current).max(0.0);
+        health.current += applied;
+
+        HpGain {
+            applied,
+            overheal: amount - applied,
         }
     }
 
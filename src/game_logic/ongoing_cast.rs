@@ -3,7 +3,10 @@ use bevy::{
     prelude::*,
 };
 
-use super::fight::FightInterface;
+use super::{
+    fight::FightInterface,
+    resource_pools::{AbilityCost, CostPayment, ResourcePoolsInterface},
+};
 use crate::{PerUpdateSet, game_logic::ability_slots::AbilitySlot, utils::holds_held::Held};
 
 // TODO:
@@ -29,12 +32,34 @@ pub struct OngoingCastFinishedSuccessfully {
     pub cast_target: Option<Entity>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum CastAbortReason {
+    /// Interrupted by another character (e.g., with an interrupt ability) or an effect.
+    Interrupted { by: Option<Entity> },
+    /// A new cast was started on the same slot.
+    Replaced,
+    /// The caster cancelled the cast.
+    CancelledByOwner,
+    /// The caster can't pay the [`CostPayment::OnCastFinish`] cost of the ability (anymore) when
+    /// the cast finishes.
+    InsufficientResource,
+    /// The `OngoingCast` was removed without a reason, e.g., because the slot was despawned.
+    Removed,
+}
+
 #[derive(Debug, Reflect, EntityEvent)]
 pub struct OngoingCastAborted {
     #[event_target]
     pub target: Entity,
+    pub ability_entity: Entity,
+    pub caster_entity: Option<Entity>,
+    pub reason: CastAbortReason,
 }
 
+/// Set on a slot right before its `OngoingCast` is removed, so the abort can carry the reason.
+#[derive(Debug, Component)]
+struct PendingCastAbortReason(CastAbortReason);
+
 #[derive(SystemParam)]
 pub struct OngoingCastInterface<'w, 's> {
     ongoing_casts: Query<'w, 's, &'static OngoingCast>,
@@ -43,7 +68,12 @@ pub struct OngoingCastInterface<'w, 's> {
 
 impl<'w, 's> OngoingCastInterface<'w, 's> {
     pub fn start_new_cast(&mut self, slot_e: Entity, cast: OngoingCast) -> Entity {
-        self.commands.entity(slot_e).insert(cast).id()
+        self.commands
+            .entity(slot_e)
+            .insert(PendingCastAbortReason(CastAbortReason::Replaced))
+            .insert(cast)
+            .remove::<PendingCastAbortReason>()
+            .id()
     }
 
     /// Retrieves the [`OngoingCast`] for an `AbilitySlot` entity, if it has one
@@ -52,14 +82,23 @@ impl<'w, 's> OngoingCastInterface<'w, 's> {
     }
 
     /// Cancels any ongoing cast on the specified entity (currently `AbilitySlot` entities)
-    pub fn cancel_ongoing_cast(&mut self, slot_e: Entity) {
-        self.commands.entity(slot_e).remove::<OngoingCast>();
+    pub fn cancel_ongoing_cast(&mut self, slot_e: Entity, reason: CastAbortReason) {
+        self.commands
+            .entity(slot_e)
+            .insert(PendingCastAbortReason(reason))
+            .remove::<OngoingCast>()
+            .remove::<PendingCastAbortReason>();
     }
 }
 
+/// Ticks all [`OngoingCast`]s in unpaused fights. Finished casts pay the
+/// [`CostPayment::OnCastFinish`] cost of their ability before [`OngoingCastFinishedSuccessfully`]
+/// is triggered, and are aborted instead, if the caster can't pay it.
 fn tick_ongoing_casts(
     mut ongoing_casts: Query<(Entity, &mut OngoingCast)>,
     held_slots: Query<&Held<AbilitySlot>>,
+    ability_costs: Query<&AbilityCost>,
+    mut resource_pools_interface: ResourcePoolsInterface,
     fight_interface: FightInterface,
     time: Res<Time>,
     mut commands: Commands,
@@ -78,6 +117,25 @@ fn tick_ongoing_casts(
         ongoing_cast.cast_timer.tick(time.delta());
 
         if ongoing_cast.cast_timer.just_finished() {
+            // the cost was only checked when the cast started, e.g., another cast might have spent
+            // the resource since then. paid right away, so it can't be spent twice.
+            if let Ok(cost) = ability_costs.get(ongoing_cast.ability_e)
+                && cost.payment == CostPayment::OnCastFinish
+                && let Some(caster_e) = ongoing_cast.caster_e
+                && resource_pools_interface.pay(caster_e, cost).is_err()
+            {
+                debug!("Caster can't afford the cost of the finished cast: {ongoing_cast:?}");
+                // the `on_replace` hook only triggers this for unfinished casts
+                commands.trigger(OngoingCastAborted {
+                    target: slot_e,
+                    ability_entity: ongoing_cast.ability_e,
+                    caster_entity: ongoing_cast.caster_e,
+                    reason: CastAbortReason::InsufficientResource,
+                });
+                commands.entity(slot_e).remove::<OngoingCast>();
+                continue;
+            }
+
             commands.trigger(OngoingCastFinishedSuccessfully {
                 slot_entity: slot_e,
                 ability_entity: ongoing_cast.ability_e,
@@ -100,9 +158,17 @@ fn on_replace_ongoing_cast(mut world: DeferredWorld, hook_context: HookContext)
         // TODO: maybe consumers should also listen for `OnReplaced<OngoingCast>`, instead of this
         // event? and then have a method like `OngoingCast::finished_successfully()`, that will
         // return `false` (or `Aborted` etc.) in this case.
-        world.trigger(OngoingCastAborted {
+        let reason = world
+            .get::<PendingCastAbortReason>(ongoing_cast_e)
+            .map_or(CastAbortReason::Removed, |pending| pending.0);
+
+        let event = OngoingCastAborted {
             target: ongoing_cast_e,
-        });
+            ability_entity: ongoing_cast.ability_e,
+            caster_entity: ongoing_cast.caster_e,
+            reason,
+        };
+        world.trigger(event);
     }
 }
 
@@ -111,10 +177,12 @@ pub struct OngoingCastPlugin;
 
 impl Plugin for OngoingCastPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<OngoingCast>().add_systems(
-            FixedUpdate,
-            tick_ongoing_casts.in_set(PerUpdateSet::LogicUpdate),
-        );
+        app.register_type::<OngoingCast>()
+            .register_type::<CastAbortReason>()
+            .add_systems(
+                FixedUpdate,
+                tick_ongoing_casts.in_set(PerUpdateSet::LogicUpdate),
+            );
 
         app.world_mut()
             .register_component_hooks::<OngoingCast>()
@@ -126,15 +194,18 @@ impl Plugin for OngoingCastPlugin {
 mod tests {
     use std::time::Duration;
 
-    use bevy::prelude::*;
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
 
-    use super::{OngoingCast, OngoingCastPlugin};
-    use crate::game_logic::{
-        ability::{Ability, AbilityCooldown, AbilityId},
-        ability_casting::AbilityCastingPlugin,
-        ability_slots::{AbilitySlot, AbilitySlotType},
-        commands::CommandsPlugin,
-        cooldown::Cooldown,
+    use super::{CastAbortReason, OngoingCast, OngoingCastAborted, OngoingCastPlugin};
+    use crate::{
+        game_logic::{
+            ability::{Ability, AbilityCooldown, AbilityId},
+            ability_casting::{AbilityCastingInterface, AbilityCastingPlugin},
+            ability_slots::{AbilitySlot, AbilitySlotType},
+            commands::CommandsPlugin,
+            cooldown::Cooldown,
+        },
+        test_utils::{TestFightEntities, spawn_test_fight},
     };
 
     #[test]
@@ -189,4 +260,58 @@ mod tests {
             "Slot should NOT have Cooldown component"
         );
     }
+
+    #[test]
+    fn test_interrupt_carries_reason_and_locks_out_slot() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(AbilityCastingPlugin)
+            .add_plugins(OngoingCastPlugin);
+
+        let TestFightEntities {
+            fight_e: _,
+            caster_e,
+            slot_e,
+            ability_e,
+            enemy_e,
+        } = spawn_test_fight(&mut app);
+
+        app.world_mut().entity_mut(slot_e).insert(OngoingCast {
+            ability_e,
+            caster_e: Some(caster_e),
+            target: Some(enemy_e),
+            cast_timer: Timer::from_seconds(2.0, TimerMode::Once),
+        });
+
+        #[derive(Resource, Default)]
+        struct AbortReasons(Vec<CastAbortReason>);
+
+        app.init_resource::<AbortReasons>().add_observer(
+            |trigger: On<OngoingCastAborted>, mut reasons: ResMut<AbortReasons>| {
+                reasons.0.push(trigger.event().reason);
+            },
+        );
+
+        let interrupted = app
+            .world_mut()
+            .run_system_once(move |mut interface: AbilityCastingInterface| {
+                interface.interrupt_casts_of(caster_e, Some(enemy_e), Some(Duration::from_secs(3)))
+            })
+            .unwrap();
+
+        assert!(interrupted);
+        assert!(app.world().get::<OngoingCast>(slot_e).is_none());
+        assert_eq!(
+            app.world().resource::<AbortReasons>().0,
+            [CastAbortReason::Interrupted { by: Some(enemy_e) }]
+        );
+        assert_eq!(
+            app.world()
+                .get::<Cooldown>(slot_e)
+                .unwrap()
+                .remaining_cooldown(),
+            Duration::from_secs(3)
+        );
+    }
 }
@@ -0,0 +1,369 @@
+use std::collections::BTreeMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::Deserialize;
+
+use crate::{PerUpdateSet, game_logic::fight::FightInterface};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect, Deserialize)]
+pub enum ResourceType {
+    Mana,
+    Stamina,
+}
+
+impl std::fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceType::Mana => write!(f, "Mana"),
+            ResourceType::Stamina => write!(f, "Stamina"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Reflect)]
+pub struct ResourcePool {
+    current: f64,
+    max: f64,
+    regen_per_sec: f64,
+}
+
+impl ResourcePool {
+    pub fn new(current_and_max: f64, regen_per_sec: f64) -> Self {
+        Self {
+            current: current_and_max,
+            max: current_and_max,
+            regen_per_sec,
+        }
+    }
+
+    pub fn current(&self) -> f64 {
+        self.current
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    pub fn regen_per_sec(&self) -> f64 {
+        self.regen_per_sec
+    }
+
+    fn gain(&mut self, amount: f64) {
+        self.current = (self.current + amount).min(self.max);
+    }
+}
+
+/// The resource pools (mana, stamina, ..) of a character.
+#[derive(Debug, Clone, Default, Component, Reflect)]
+pub struct ResourcePools(BTreeMap<ResourceType, ResourcePool>);
+
+impl ResourcePools {
+    pub fn new(pools: impl IntoIterator<Item = (ResourceType, ResourcePool)>) -> Self {
+        Self(pools.into_iter().collect())
+    }
+
+    pub fn get(&self, resource: ResourceType) -> Option<&ResourcePool> {
+        self.0.get(&resource)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ResourceType, &ResourcePool)> {
+        self.0.iter().map(|(resource, pool)| (*resource, pool))
+    }
+}
+
+/// When the [`AbilityCost`] of an ability is paid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Deserialize)]
+pub enum CostPayment {
+    /// Paid when the cast starts, refunded if the cast is aborted.
+    OnCastStart,
+    /// Paid when the cast finishes, the cast is aborted if the caster can't pay it (anymore).
+    #[default]
+    OnCastFinish,
+}
+
+/// The resource cost for using an ability, should be on the same entity as an `Ability`.
+#[derive(Debug, Clone, PartialEq, Component, Reflect, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AbilityCost {
+    pub resource: ResourceType,
+    pub amount: f64,
+    #[serde(default)]
+    pub payment: CostPayment,
+}
+
+#[derive(Debug)]
+pub struct InsufficientResourceError;
+
+#[derive(SystemParam)]
+pub struct ResourcePoolsInterface<'w, 's> {
+    resource_pools: Query<'w, 's, &'static mut ResourcePools>,
+}
+
+impl<'w, 's> ResourcePoolsInterface<'w, 's> {
+    pub fn can_afford(&self, character: Entity, cost: &AbilityCost) -> bool {
+        self.resource_pools
+            .get(character)
+            .ok()
+            .and_then(|pools| pools.get(cost.resource))
+            .is_some_and(|pool| pool.current >= cost.amount)
+    }
+
+    pub fn pay(
+        &mut self,
+        character: Entity,
+        cost: &AbilityCost,
+    ) -> Result<(), InsufficientResourceError> {
+        if !self.can_afford(character, cost) {
+            return Err(InsufficientResourceError);
+        }
+
+        let mut pools = self.resource_pools.get_mut(character).unwrap();
+        pools.0.get_mut(&cost.resource).unwrap().current -= cost.amount;
+
+        Ok(())
+    }
+
+    /// Gives back a previously paid `cost`, clamped to the pool's maximum.
+    pub fn refund(&mut self, character: Entity, cost: &AbilityCost) {
+        if let Ok(mut pools) = self.resource_pools.get_mut(character)
+            && let Some(pool) = pools.0.get_mut(&cost.resource)
+        {
+            pool.gain(cost.amount);
+        }
+    }
+}
+
+fn regenerate_resources(
+    resource_pools: Query<(Entity, &mut ResourcePools)>,
+    fight_interface: FightInterface,
+    time: Res<Time>,
+) {
+    for (character, mut pools) in resource_pools {
+        let fight_e = fight_interface.get_fight_of_entity(character);
+
+        if fight_interface.is_fight_paused(fight_e) {
+            continue;
+        }
+
+        for pool in pools.0.values_mut() {
+            let regen = pool.regen_per_sec * time.delta_secs_f64();
+            pool.gain(regen);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ResourcePoolsPlugin;
+
+impl Plugin for ResourcePoolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ResourcePools>()
+            .register_type::<AbilityCost>()
+            .add_systems(
+                FixedUpdate,
+                regenerate_resources.in_set(PerUpdateSet::LogicUpdate),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+    use super::{AbilityCost, CostPayment, ResourcePool, ResourcePools, ResourceType};
+    use crate::{
+        game_logic::{
+            ability::{Ability, AbilityId, PerformAbility},
+            ability_casting::AbilityCastingPlugin,
+            commands::CommandsPlugin,
+            cooldown::Cooldown,
+            fight::{FightPlugin, FightTime},
+            ongoing_cast::{CastAbortReason, OngoingCast, OngoingCastAborted, OngoingCastPlugin},
+        },
+        test_utils::{TestFightEntities, spawn_test_fight},
+    };
+
+    fn mana(app: &App, character_e: Entity) -> f64 {
+        app.world()
+            .get::<ResourcePools>(character_e)
+            .unwrap()
+            .get(ResourceType::Mana)
+            .unwrap()
+            .current()
+    }
+
+    fn spawn_caster_and_ability(app: &mut App, payment: CostPayment) -> (Entity, Entity) {
+        let caster_e = app
+            .world_mut()
+            .spawn(ResourcePools::new([(
+                ResourceType::Mana,
+                ResourcePool::new(100.0, 0.0),
+            )]))
+            .id();
+        let ability_e = app
+            .world_mut()
+            .spawn((
+                Ability {
+                    id: AbilityId::NeedlingHex,
+                    name: "Needling Hex".into(),
+                    description: "Needling Hex".into(),
+                },
+                AbilityCost {
+                    resource: ResourceType::Mana,
+                    amount: 40.0,
+                    payment,
+                },
+            ))
+            .id();
+
+        (caster_e, ability_e)
+    }
+
+    #[derive(Debug, Default, Resource)]
+    struct CastOutcomes {
+        performed: usize,
+        aborted: Vec<CastAbortReason>,
+    }
+
+    /// Starts a 100ms cast of the test fight's ability, which costs 40 mana paid on cast finish.
+    fn start_cast_with_cost_on_finish(app: &mut App) -> TestFightEntities {
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(FightPlugin)
+            .add_plugins(AbilityCastingPlugin)
+            .add_plugins(OngoingCastPlugin)
+            .init_resource::<CastOutcomes>()
+            .add_observer(
+                |_trigger: On<PerformAbility>, mut outcomes: ResMut<CastOutcomes>| {
+                    outcomes.performed += 1;
+                },
+            )
+            .add_observer(
+                |trigger: On<OngoingCastAborted>, mut outcomes: ResMut<CastOutcomes>| {
+                    outcomes.aborted.push(trigger.event().reason);
+                },
+            );
+
+        let entities = spawn_test_fight(app);
+        app.world_mut()
+            .get_mut::<FightTime>(entities.fight_e)
+            .unwrap()
+            .set_paused(false);
+        app.world_mut()
+            .entity_mut(entities.caster_e)
+            .insert(ResourcePools::new([(
+                ResourceType::Mana,
+                ResourcePool::new(100.0, 0.0),
+            )]));
+        app.world_mut()
+            .entity_mut(entities.ability_e)
+            .insert(AbilityCost {
+                resource: ResourceType::Mana,
+                amount: 40.0,
+                payment: CostPayment::OnCastFinish,
+            });
+        app.world_mut()
+            .entity_mut(entities.slot_e)
+            .insert(OngoingCast {
+                ability_e: entities.ability_e,
+                caster_e: Some(entities.caster_e),
+                target: Some(entities.enemy_e),
+                cast_timer: Timer::new(Duration::from_millis(100), TimerMode::Once),
+            });
+
+        app.update();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            65,
+        )));
+
+        entities
+    }
+
+    #[test]
+    fn test_cost_paid_on_cast_finish() {
+        let mut app = App::new();
+        let TestFightEntities { caster_e, .. } = start_cast_with_cost_on_finish(&mut app);
+
+        app.update();
+        assert_eq!(
+            mana(&app, caster_e),
+            100.0,
+            "nothing is paid before the cast finishes"
+        );
+
+        app.update();
+        assert_eq!(mana(&app, caster_e), 60.0);
+        assert_eq!(app.world().resource::<CastOutcomes>().performed, 1);
+    }
+
+    #[test]
+    fn test_cast_aborted_if_cost_cant_be_paid_on_cast_finish() {
+        let mut app = App::new();
+        let TestFightEntities {
+            caster_e,
+            slot_e,
+            ability_e,
+            ..
+        } = start_cast_with_cost_on_finish(&mut app);
+
+        app.update();
+
+        // drain the pool mid-cast
+        app.world_mut()
+            .get_mut::<ResourcePools>(caster_e)
+            .unwrap()
+            .0
+            .get_mut(&ResourceType::Mana)
+            .unwrap()
+            .current = 10.0;
+
+        app.update();
+
+        let outcomes = app.world().resource::<CastOutcomes>();
+        assert_eq!(outcomes.performed, 0, "the ability must not be performed");
+        assert_eq!(outcomes.aborted, [CastAbortReason::InsufficientResource]);
+        assert_eq!(mana(&app, caster_e), 10.0);
+        assert!(app.world().get::<OngoingCast>(slot_e).is_none());
+        assert!(
+            app.world().get::<Cooldown>(slot_e).is_none(),
+            "aborted casts don't put the slot on cooldown"
+        );
+        assert!(
+            app.world().get::<Cooldown>(ability_e).is_none(),
+            "aborted casts don't put the ability on cooldown"
+        );
+    }
+
+    #[test]
+    fn test_cost_paid_on_cast_start_is_refunded_on_abort() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(CommandsPlugin)
+            .add_plugins(AbilityCastingPlugin)
+            .add_plugins(OngoingCastPlugin);
+
+        let (caster_e, ability_e) = spawn_caster_and_ability(&mut app, CostPayment::OnCastStart);
+        let slot_e = app.world_mut().spawn_empty().id();
+
+        app.world_mut()
+            .get_mut::<ResourcePools>(caster_e)
+            .unwrap()
+            .0
+            .get_mut(&ResourceType::Mana)
+            .unwrap()
+            .current = 80.0;
+
+        app.world_mut().trigger(OngoingCastAborted {
+            target: slot_e,
+            ability_entity: ability_e,
+            caster_entity: Some(caster_e),
+            reason: CastAbortReason::CancelledByOwner,
+        });
+        app.update();
+
+        // refunds are clamped to the maximum
+        assert_eq!(mana(&app, caster_e), 100.0);
+    }
+}
@@ -16,10 +16,19 @@ impl FiniteRepeatingTimer {
         }
     }
 
+    pub fn tick_interval(&self) -> Duration {
+        self.timer.duration()
+    }
+
     pub fn remaining_ticks(&self) -> u32 {
         self.remaining_ticks
     }
 
+    /// Adds `num_ticks` to the remaining ticks.
+    pub fn add_ticks(&mut self, num_ticks: u32) {
+        self.remaining_ticks += num_ticks;
+    }
+
     pub fn is_finished(&self) -> bool {
         self.remaining_ticks() == 0
     }